@@ -0,0 +1,36 @@
+use std::{env, process};
+
+use knightime::{board::Board, perft::Perft};
+
+extern crate knightime;
+
+// usage: perft <depth> [fen], the output matches the `go perft` command of Stockfish
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let depth = match args.first().map(|depth| depth.parse::<u64>()) {
+        Some(Ok(depth)) => depth,
+        _ => {
+            eprintln!("usage: perft <depth> [fen]");
+            process::exit(1);
+        }
+    };
+    // the fen may be given quoted or as separate arguments
+    let board = match args.len() {
+        1 => Board::default(),
+        _ => match Board::from_fen(&args[1..].join(" ")) {
+            Ok(board) => board,
+            Err(err) => {
+                eprintln!("invalid fen: {:?}", err);
+                process::exit(1);
+            }
+        },
+    };
+
+    let mut perft = Perft::new(board);
+    for (mv, nodes) in perft.divide(depth) {
+        println!("{}: {}", mv.to_uci_string(), nodes);
+    }
+    println!();
+    println!("Nodes searched: {}", perft.nodes);
+}
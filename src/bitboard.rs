@@ -5,15 +5,9 @@ use std::{
 
 use crate::defs::Square;
 
-#[derive(PartialEq, PartialOrd, Clone, Copy, Debug)]
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug, Default)]
 pub struct Bitboard(pub u64);
 
-impl Default for Bitboard {
-    fn default() -> Self {
-        Self(0x0)
-    }
-}
-
 impl fmt::Display for Bitboard {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f)?;
@@ -155,11 +149,8 @@ impl Iterator for Bitboard {
 
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.get_ls1b_index();
-        match next {
-            Some(square) => {
-                self.clear_square(square);
-            }
-            None => {}
+        if let Some(square) = next {
+            self.clear_square(square);
         }
         next
     }
@@ -198,10 +189,10 @@ impl Bitboard {
 
     pub fn get_ls1b_index(&self) -> Option<u32> {
         let count = self.0.trailing_zeros();
-        return match count.cmp(&64) {
+        match count.cmp(&64) {
             std::cmp::Ordering::Less => Some(count),
             _ => None,
-        };
+        }
     }
 
     pub fn get_blocker_boards(&self) -> Vec<Bitboard> {
@@ -231,24 +222,24 @@ mod tests {
     fn get_square_tests() {
         let bitboard = Bitboard::default();
         for index in 0..64 {
-            assert_eq!(bitboard.get_square(index), false);
+            assert!(!bitboard.get_square(index));
         }
 
         let bitboard = Bitboard::new(0x301);
 
-        assert_eq!(bitboard.get_square(A1), true);
-        assert_eq!(bitboard.get_square(A2), true);
-        assert_eq!(bitboard.get_square(B2), true);
-        assert_eq!(bitboard.get_square(C5), false);
+        assert!(bitboard.get_square(A1));
+        assert!(bitboard.get_square(A2));
+        assert!(bitboard.get_square(B2));
+        assert!(!bitboard.get_square(C5));
     }
 
     #[test]
     fn set_square_tests() {
         let mut bitboard = Bitboard::default();
         bitboard.set_square(A1);
-        assert_eq!(bitboard.get_square(A1), true);
+        assert!(bitboard.get_square(A1));
         bitboard.set_square(B2);
-        assert_eq!(bitboard.get_square(B2), true);
+        assert!(bitboard.get_square(B2));
         bitboard.set_square(C1);
         bitboard.set_square(C3);
         assert_eq!(bitboard.0, 0x40205);
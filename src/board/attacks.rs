@@ -2,10 +2,13 @@ use crate::{
     bitboard::Bitboard,
     constants::*,
     defs::{CastleRights, Piece, Promotion, Side, Square},
-    move_generator::movelist::{Move, MoveList},
+    move_generator::{
+        movelist::{Move, MoveList},
+        MoveGenerator,
+    },
 };
 
-use super::Board;
+use super::{defs::MoveType, Board};
 
 impl Board {
     pub fn get_attacked_squares(&self, side: Side) -> Bitboard {
@@ -20,48 +23,40 @@ impl Board {
 
     pub fn is_square_attacked(&self, square: Square, side: Side) -> bool {
         if (self.state.bitboards[side as usize][Piece::Pawn as usize]
-            & self
-                .move_generator
-                .get_pawn_attacks(square, side.get_opposite_side()))
+            & MoveGenerator::instance().get_pawn_attacks(square, side.get_opposite_side()))
             != 0
         {
             return true;
         }
         if (self.state.bitboards[side as usize][Piece::Knight as usize]
-            & self.move_generator.get_knight_attacks(square))
+            & MoveGenerator::instance().get_knight_attacks(square))
             != 0
         {
             return true;
         }
         if (self.state.bitboards[side as usize][Piece::King as usize]
-            & self.move_generator.get_king_attacks(square))
+            & MoveGenerator::instance().get_king_attacks(square))
             != 0
         {
             return true;
         }
 
         if (self.state.bitboards[side as usize][Piece::Bishop as usize]
-            & self
-                .move_generator
-                .get_bishop_attacks(square, self.state.occupancies[2]))
+            & MoveGenerator::instance().get_bishop_attacks(square, self.state.occupancies[2]))
             != 0
         {
             return true;
         }
 
         if (self.state.bitboards[side as usize][Piece::Rook as usize]
-            & self
-                .move_generator
-                .get_rook_attacks(square, self.state.occupancies[2]))
+            & MoveGenerator::instance().get_rook_attacks(square, self.state.occupancies[2]))
             != 0
         {
             return true;
         }
 
         if (self.state.bitboards[side as usize][Piece::Queen as usize]
-            & self
-                .move_generator
-                .get_queen_attacks(square, self.state.occupancies[2]))
+            & MoveGenerator::instance().get_queen_attacks(square, self.state.occupancies[2]))
             != 0
         {
             return true;
@@ -71,39 +66,74 @@ impl Board {
     }
 
     pub fn generate_moves(&self) -> MoveList {
+        self.generate_moves_of_type(MoveType::All)
+    }
+
+    // captures, en passant and every promotion
+    pub fn generate_captures(&self) -> MoveList {
+        self.generate_moves_of_type(MoveType::Captures)
+    }
+
+    // everything generate_captures leaves out, castling included
+    pub fn generate_quiets(&self) -> MoveList {
+        self.generate_moves_of_type(MoveType::Quiets)
+    }
+
+    fn generate_moves_of_type(&self, move_type: MoveType) -> MoveList {
         let side = self.state.side_to_move;
         let mut movelist = MoveList::new();
-        movelist.append_moves(&mut self.generate_pawn_moves(side));
-        movelist.append_moves(&mut self.generate_castling_moves(side));
-        movelist.append_moves(&mut self.generate_knight_moves(side));
-        movelist.append_moves(&mut self.generate_king_moves(side));
-        movelist.append_moves(&mut self.generate_bishop_moves(side));
-        movelist.append_moves(&mut self.generate_rook_moves(side));
-        movelist.append_moves(&mut self.generate_queen_moves(side));
+        movelist.append_moves(&mut self.generate_pawn_moves(side, move_type));
+        if move_type != MoveType::Captures {
+            movelist.append_moves(&mut self.generate_castling_moves(side));
+        }
+        movelist.append_moves(&mut self.generate_knight_moves(side, move_type));
+        movelist.append_moves(&mut self.generate_king_moves(side, move_type));
+        movelist.append_moves(&mut self.generate_bishop_moves(side, move_type));
+        movelist.append_moves(&mut self.generate_rook_moves(side, move_type));
+        movelist.append_moves(&mut self.generate_queen_moves_of_type(side, move_type));
 
         movelist
     }
 
-    fn generate_pawn_moves(&self, side: Side) -> MoveList {
+    // squares a piece other than a pawn may move to
+    fn get_target_squares(&self, side: Side, move_type: MoveType) -> Bitboard {
+        match move_type {
+            MoveType::All => !self.state.occupancies[side as usize],
+            MoveType::Captures => self.state.occupancies[side.get_opposite_side() as usize],
+            MoveType::Quiets => !self.state.occupancies[2],
+        }
+    }
+
+    fn generate_pawn_moves(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
         let bitboard = self.state.bitboards[side as usize][Piece::Pawn as usize];
 
         for square in bitboard.into_iter() {
-            movelist.append_moves(&mut self.generate_quiet_pawn_move(square, side));
-            movelist.append_moves(&mut self.generate_noisy_pawn_move(square, side));
+            movelist.append_moves(&mut self.generate_quiet_pawn_move(square, side, move_type));
+            if move_type != MoveType::Quiets {
+                movelist.append_moves(&mut self.generate_noisy_pawn_move(square, side));
+            }
         }
         movelist
     }
 
-    fn generate_quiet_pawn_move(&self, square: Square, side: Side) -> MoveList {
+    fn generate_quiet_pawn_move(
+        &self,
+        square: Square,
+        side: Side,
+        move_type: MoveType,
+    ) -> MoveList {
         let mut movelist = MoveList::new();
         let target_square = match side {
             Side::White => square + 8,
             Side::Black => square - 8,
         };
-        if !(A2..=H7).contains(&target_square)
-            && !self.state.occupancies[2].get_square(target_square)
-        {
+        if !(A2..=H7).contains(&target_square) {
+            // promotions are generated along with captures
+            if move_type == MoveType::Quiets || self.state.occupancies[2].get_square(target_square)
+            {
+                return movelist;
+            }
             #[rustfmt::skip]
             movelist.add_move(Move::new(square, target_square, Piece::Pawn, Some(Promotion::Queen), false, false, false, false,));
             #[rustfmt::skip]
@@ -112,7 +142,7 @@ impl Board {
             movelist.add_move(Move::new( square, target_square, Piece::Pawn, Some(Promotion::Bishop), false, false, false, false,));
             #[rustfmt::skip]
             movelist.add_move(Move::new( square, target_square, Piece::Pawn, Some(Promotion::Knight), false, false, false, false,));
-        } else {
+        } else if move_type != MoveType::Captures {
             if !self.state.occupancies[2].get_square(target_square) {
                 #[rustfmt::skip]
                 movelist.add_move(Move::new( square, target_square, Piece::Pawn, None, false, false, false, false,));
@@ -147,7 +177,7 @@ impl Board {
 
     fn generate_noisy_pawn_move(&self, square: Square, side: Side) -> MoveList {
         let mut movelist = MoveList::new();
-        let attacks = self.move_generator.get_pawn_attacks(square, side)
+        let attacks = MoveGenerator::instance().get_pawn_attacks(square, side)
             & self.state.occupancies[side.get_opposite_side() as usize];
 
         for target_square in attacks.into_iter() {
@@ -166,17 +196,14 @@ impl Board {
                 movelist.add_move(Move::new( square, target_square, Piece::Pawn, None, true, false, false, false,));
             }
         }
-        match self.state.en_passant_square {
-            Some(en_passant_square) => {
-                let en_passant_attacks = self.move_generator.get_pawn_attacks(square, side)
-                    & (Bitboard(1u64) << en_passant_square);
-
-                for target_square in en_passant_attacks.into_iter() {
-                    #[rustfmt::skip]
-                    movelist.add_move(Move::new( square, target_square, Piece::Pawn, None, true, false, true, false,));
-                }
+        if let Some(en_passant_square) = self.state.en_passant_square {
+            let en_passant_attacks = MoveGenerator::instance().get_pawn_attacks(square, side)
+                & (Bitboard(1u64) << en_passant_square);
+
+            for target_square in en_passant_attacks.into_iter() {
+                #[rustfmt::skip]
+                movelist.add_move(Move::new( square, target_square, Piece::Pawn, None, true, false, true, false,));
             }
-            None => {}
         }
         movelist
     }
@@ -241,11 +268,13 @@ impl Board {
         movelist
     }
 
-    fn generate_knight_moves(&self, side: Side) -> MoveList {
+    fn generate_knight_moves(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
+        let targets = self.get_target_squares(side, move_type);
         for square in self.state.bitboards[side as usize][Piece::Knight as usize].into_iter() {
-            let quiet_moves =
-                self.move_generator.get_knight_attacks(square) & !self.state.occupancies[2];
+            let quiet_moves = MoveGenerator::instance().get_knight_attacks(square)
+                & !self.state.occupancies[2]
+                & targets;
 
             for target_square in quiet_moves.into_iter() {
                 movelist.add_move(Move::new(
@@ -260,8 +289,9 @@ impl Board {
                 ));
             }
 
-            let captures = self.move_generator.get_knight_attacks(square)
-                & self.state.occupancies[side.get_opposite_side() as usize];
+            let captures = MoveGenerator::instance().get_knight_attacks(square)
+                & self.state.occupancies[side.get_opposite_side() as usize]
+                & targets;
 
             for target_square in captures.into_iter() {
                 movelist.add_move(Move::new(
@@ -279,11 +309,13 @@ impl Board {
         movelist
     }
 
-    fn generate_king_moves(&self, side: Side) -> MoveList {
+    fn generate_king_moves(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
+        let targets = self.get_target_squares(side, move_type);
         for square in self.state.bitboards[side as usize][Piece::King as usize].into_iter() {
-            let quiet_moves =
-                self.move_generator.get_king_attacks(square) & !self.state.occupancies[2];
+            let quiet_moves = MoveGenerator::instance().get_king_attacks(square)
+                & !self.state.occupancies[2]
+                & targets;
 
             for target_square in quiet_moves.into_iter() {
                 movelist.add_move(Move::new(
@@ -298,8 +330,9 @@ impl Board {
                 ));
             }
 
-            let captures = self.move_generator.get_king_attacks(square)
-                & self.state.occupancies[side.get_opposite_side() as usize];
+            let captures = MoveGenerator::instance().get_king_attacks(square)
+                & self.state.occupancies[side.get_opposite_side() as usize]
+                & targets;
 
             for target_square in captures.into_iter() {
                 movelist.add_move(Move::new(
@@ -317,13 +350,14 @@ impl Board {
         movelist
     }
 
-    fn generate_bishop_moves(&self, side: Side) -> MoveList {
+    fn generate_bishop_moves(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
+        let targets = self.get_target_squares(side, move_type);
         for square in self.state.bitboards[side as usize][Piece::Bishop as usize].into_iter() {
-            let quiet_moves = self
-                .move_generator
+            let quiet_moves = MoveGenerator::instance()
                 .get_bishop_attacks(square, self.state.occupancies[2])
-                & !self.state.occupancies[2];
+                & !self.state.occupancies[2]
+                & targets;
 
             for target_square in quiet_moves.into_iter() {
                 movelist.add_move(Move::new(
@@ -338,10 +372,10 @@ impl Board {
                 ));
             }
 
-            let captures = self
-                .move_generator
+            let captures = MoveGenerator::instance()
                 .get_bishop_attacks(square, self.state.occupancies[2])
-                & self.state.occupancies[side.get_opposite_side() as usize];
+                & self.state.occupancies[side.get_opposite_side() as usize]
+                & targets;
 
             for target_square in captures.into_iter() {
                 movelist.add_move(Move::new(
@@ -359,13 +393,14 @@ impl Board {
         movelist
     }
 
-    fn generate_rook_moves(&self, side: Side) -> MoveList {
+    fn generate_rook_moves(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
+        let targets = self.get_target_squares(side, move_type);
         for square in self.state.bitboards[side as usize][Piece::Rook as usize].into_iter() {
-            let quiet_moves = self
-                .move_generator
+            let quiet_moves = MoveGenerator::instance()
                 .get_rook_attacks(square, self.state.occupancies[2])
-                & !self.state.occupancies[2];
+                & !self.state.occupancies[2]
+                & targets;
 
             for target_square in quiet_moves.into_iter() {
                 movelist.add_move(Move::new(
@@ -380,10 +415,10 @@ impl Board {
                 ));
             }
 
-            let captures = self
-                .move_generator
+            let captures = MoveGenerator::instance()
                 .get_rook_attacks(square, self.state.occupancies[2])
-                & self.state.occupancies[side.get_opposite_side() as usize];
+                & self.state.occupancies[side.get_opposite_side() as usize]
+                & targets;
 
             for target_square in captures.into_iter() {
                 movelist.add_move(Move::new(
@@ -402,12 +437,17 @@ impl Board {
     }
 
     pub fn generate_queen_moves(&self, side: Side) -> MoveList {
+        self.generate_queen_moves_of_type(side, MoveType::All)
+    }
+
+    fn generate_queen_moves_of_type(&self, side: Side, move_type: MoveType) -> MoveList {
         let mut movelist = MoveList::new();
+        let targets = self.get_target_squares(side, move_type);
         for square in self.state.bitboards[side as usize][Piece::Queen as usize].into_iter() {
-            let quiet_moves = self
-                .move_generator
+            let quiet_moves = MoveGenerator::instance()
                 .get_queen_attacks(square, self.state.occupancies[2])
-                & !self.state.occupancies[2];
+                & !self.state.occupancies[2]
+                & targets;
 
             for target_square in quiet_moves.into_iter() {
                 movelist.add_move(Move::new(
@@ -422,10 +462,10 @@ impl Board {
                 ));
             }
 
-            let captures = self
-                .move_generator
+            let captures = MoveGenerator::instance()
                 .get_queen_attacks(square, self.state.occupancies[2])
-                & self.state.occupancies[side.get_opposite_side() as usize];
+                & self.state.occupancies[side.get_opposite_side() as usize]
+                & targets;
 
             for target_square in captures.into_iter() {
                 movelist.add_move(Move::new(
@@ -452,16 +492,16 @@ mod tests {
 
     #[test]
     fn is_square_attacked_tests() {
-        let board = Board::from_fen("8/8/8/3P4/8/8/8/8 w - - 0 1").unwrap();
-        assert_eq!(board.is_square_attacked(C6, Side::White), true);
-        assert_eq!(board.is_square_attacked(E6, Side::White), true);
-        assert_eq!(board.is_square_attacked(B6, Side::White), false);
+        let board = Board::from_fen("7k/8/8/3P4/8/8/8/K7 w - - 0 1").unwrap();
+        assert!(board.is_square_attacked(C6, Side::White));
+        assert!(board.is_square_attacked(E6, Side::White));
+        assert!(!board.is_square_attacked(B6, Side::White));
     }
 
     #[test]
     fn generate_simple_pawn_push_tests() {
-        let board = Board::from_fen("8/3p4/8/8/8/8/3P4/8 w - - 0 1").unwrap();
-        let pawn_moves = board.generate_quiet_pawn_move(D2, Side::White);
+        let board = Board::from_fen("7k/3p4/8/8/8/8/3P4/K7 w - - 0 1").unwrap();
+        let pawn_moves = board.generate_quiet_pawn_move(D2, Side::White, MoveType::All);
         #[rustfmt::skip]
         assert!(
             pawn_moves.contains(
@@ -473,7 +513,7 @@ mod tests {
             Move::new(D2, D4, Piece::Pawn, None, false, true, false, false)
         ));
         assert_eq!(pawn_moves.get(2), None);
-        let pawn_moves = board.generate_quiet_pawn_move(D7, Side::Black);
+        let pawn_moves = board.generate_quiet_pawn_move(D7, Side::Black, MoveType::All);
         #[rustfmt::skip]
         assert_eq!(
             pawn_moves.get(0).unwrap(),
@@ -543,7 +583,7 @@ mod tests {
     #[test]
     fn generate_pawn_push_promotion_tests() {
         let board = Board::from_fen("1k6/1P1pP3/8/8/8/8/3P1K2/8 w - - 0 1").unwrap();
-        let pawn_moves = board.generate_quiet_pawn_move(E7, Side::White);
+        let pawn_moves = board.generate_quiet_pawn_move(E7, Side::White, MoveType::All);
         #[rustfmt::skip]
         assert_eq!(
             pawn_moves.get(0).unwrap(),
@@ -565,22 +605,22 @@ mod tests {
             Move::new(E7, E8, Piece::Pawn, Some(Promotion::Knight), false, false, false, false)
         );
         assert_eq!(pawn_moves.get(4), None);
-        let pawn_moves = board.generate_quiet_pawn_move(B7, Side::White);
+        let pawn_moves = board.generate_quiet_pawn_move(B7, Side::White, MoveType::All);
         assert_eq!(pawn_moves.get(0), None);
 
         let board =
-            Board::from_fen("r3k2r/p1ppkpb1/bn2pnp1/3PN3/1p2P3/P1N2Q2/1PPBBPpP/R3K1R1 b - - 0 1")
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/P1N2Q2/1PPBBPpP/R3K1R1 b - - 0 1")
                 .unwrap();
-        let pawn_moves = board.generate_quiet_pawn_move(G2, Side::Black);
+        let pawn_moves = board.generate_quiet_pawn_move(G2, Side::Black, MoveType::All);
         assert_eq!(pawn_moves.get(0), None);
     }
 
     #[test]
     fn generate_blocked_pawn_push_tests() {
-        let board = Board::from_fen("8/3p4/3B4/8/8/3b4/3P4/8 w - - 0 1").unwrap();
-        let pawn_moves = board.generate_quiet_pawn_move(D2, Side::White);
+        let board = Board::from_fen("7k/3p4/3B4/8/8/3b4/3P4/K7 w - - 0 1").unwrap();
+        let pawn_moves = board.generate_quiet_pawn_move(D2, Side::White, MoveType::All);
         assert_eq!(pawn_moves.get(0), None);
-        let pawn_moves = board.generate_quiet_pawn_move(D7, Side::Black);
+        let pawn_moves = board.generate_quiet_pawn_move(D7, Side::Black, MoveType::All);
         assert_eq!(pawn_moves.get(0), None);
     }
 
@@ -599,9 +639,9 @@ mod tests {
             Move::new(E8, G8, Piece::King, None, false, false, false, true)
         );
         assert_eq!(castling_moves.get(1), None);
-        // here black is in check, but white can castle both sides
+        // white can castle both sides
         let board =
-            Board::from_fen("rnb1kbnr/ppp3pp/5p2/1BqpP1B1/8/2N1PN2/PPP1QPPP/R3K2R w KQkq - 0 1")
+            Board::from_fen("rnb1kbnr/pppp2pp/5p2/1BqpP1B1/8/2N1PN2/PPP1QPPP/R3K2R w KQkq - 0 1")
                 .unwrap();
         let castling_moves = board.generate_castling_moves(Side::White);
         #[rustfmt::skip]
@@ -619,7 +659,7 @@ mod tests {
         assert_eq!(castling_moves.get(1), None);
 
         let board =
-            Board::from_fen("rnb1kbnr/ppp3pp/5p2/1BqpP1B1/8/2N1PN2/PPP1QPPP/R3K2R w - - 0 1")
+            Board::from_fen("rnb1kbnr/pppp2pp/5p2/1BqpP1B1/8/2N1PN2/PPP1QPPP/R3K2R w - - 0 1")
                 .unwrap();
         let castling_moves = board.generate_castling_moves(Side::White);
         assert_eq!(castling_moves.get(0), None);
@@ -629,8 +669,8 @@ mod tests {
 
     #[test]
     fn generate_knight_moves_tests() {
-        let board = Board::from_fen("8/8/5p2/2P5/4N3/6p1/5P2/8 w - - 0 1").unwrap();
-        let knight_moves = board.generate_knight_moves(Side::White);
+        let board = Board::from_fen("7k/8/5p2/2P5/4N3/6p1/5P2/K7 w - - 0 1").unwrap();
+        let knight_moves = board.generate_knight_moves(Side::White, MoveType::All);
         #[rustfmt::skip]
         assert!(knight_moves.contains(
             Move::new(E4, D2, Piece::Knight, None, false, false, false, false)
@@ -660,8 +700,8 @@ mod tests {
 
     #[test]
     fn generate_king_moves_tests() {
-        let board = Board::from_fen("8/8/8/4n3/2pK4/2P1R3/8/8 w - - 0 1").unwrap();
-        let king_moves = board.generate_king_moves(Side::White);
+        let board = Board::from_fen("7k/8/8/4n3/2pK4/2P1R3/8/8 w - - 0 1").unwrap();
+        let king_moves = board.generate_king_moves(Side::White, MoveType::All);
         #[rustfmt::skip]
         assert!(king_moves.contains(
             Move::new(D4, D5, Piece::King, None, false, false, false, false)
@@ -691,8 +731,8 @@ mod tests {
 
     #[test]
     fn generate_bishop_moves_tests() {
-        let board = Board::from_fen("8/8/2n5/7p/8/5B2/4P3/8 w - - 0 1").unwrap();
-        let bishop_moves = board.generate_bishop_moves(Side::White);
+        let board = Board::from_fen("7k/8/2n5/7p/8/5B2/4P3/K7 w - - 0 1").unwrap();
+        let bishop_moves = board.generate_bishop_moves(Side::White, MoveType::All);
         #[rustfmt::skip]
         assert!(bishop_moves.contains(
             Move::new(F3, G2, Piece::Bishop, None, false, false, false, false)
@@ -726,8 +766,8 @@ mod tests {
 
     #[test]
     fn generate_rook_moves_tests() {
-        let board = Board::from_fen("8/8/5n2/8/8/3P1R1p/8/8 w - - 0 1").unwrap();
-        let rook_moves = board.generate_rook_moves(Side::White);
+        let board = Board::from_fen("7k/8/5n2/8/8/3P1R1p/8/K7 w - - 0 1").unwrap();
+        let rook_moves = board.generate_rook_moves(Side::White, MoveType::All);
         #[rustfmt::skip]
         assert!(rook_moves.contains(
             Move::new(F3, E3, Piece::Rook, None, false, false, false, false)
@@ -765,7 +805,7 @@ mod tests {
 
     #[test]
     fn generate_queen_moves_tests() {
-        let board = Board::from_fen("8/8/8/6P1/4n3/8/5PQr/8 w - - 0 1").unwrap();
+        let board = Board::from_fen("k7/8/8/6P1/4n3/8/5PQr/K7 w - - 0 1").unwrap();
         let queen_moves = board.generate_queen_moves(Side::White);
         #[rustfmt::skip]
         assert!(queen_moves.contains(
@@ -801,4 +841,38 @@ mod tests {
         ));
         assert_eq!(queen_moves.len(), 9);
     }
+    #[test]
+    fn generate_captures_and_quiets_tests() {
+        // kiwipete with a white pawn about to promote on b7
+        let mut board =
+            Board::from_fen("r3k2r/pPppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/P1PBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        let captures = board.generate_captures();
+        let quiets = board.generate_quiets();
+        let moves = board.generate_moves();
+
+        // both stages together give back every move exactly once
+        assert_eq!(captures.len() + quiets.len(), moves.len());
+        for mv in moves.into_iter() {
+            let noisy = mv.get_capture() || mv.get_promotion().is_some();
+            assert_eq!(captures.contains(mv), noisy);
+            assert_eq!(quiets.contains(mv), !noisy);
+        }
+        #[rustfmt::skip]
+        assert!(captures.contains(Move::new(B7, B8, Piece::Pawn, Some(Promotion::Queen), false, false, false, false)));
+        #[rustfmt::skip]
+        assert!(quiets.contains(Move::new(E1, G1, Piece::King, None, false, false, false, true)));
+
+        // make_move only accepts what generate_captures returns when asked to
+        #[rustfmt::skip]
+        let quiet = Move::new(A2, A3, Piece::Pawn, None, false, false, false, false);
+        assert!(board.make_move(quiet, true).is_err());
+        #[rustfmt::skip]
+        let promotion = Move::new(B7, B8, Piece::Pawn, Some(Promotion::Queen), false, false, false, false);
+        assert!(board.make_move(promotion, true).is_ok());
+        board.take_back_move();
+        #[rustfmt::skip]
+        let capture = Move::new(E5, F7, Piece::Knight, None, true, false, false, false);
+        assert!(board.make_move(capture, true).is_ok());
+    }
 }
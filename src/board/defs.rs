@@ -1,6 +1,43 @@
+use crate::defs::{Side, Square};
+
+// which part of the moves a generator produces
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MoveType {
+    All,
+    Captures,
+    Quiets,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FenField {
+    Placement,
+    SideToMove,
+    CastlingRights,
+    EnPassantSquare,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+// ranks are numbered from 1 to 8 and char indexes are relative to the start of their field
 #[derive(Debug, PartialEq)]
 pub enum ParseFenError {
-    UnexpectedChar,
+    UnexpectedChar {
+        field: FenField,
+        char: char,
+        index: usize,
+    },
     EmptyString,
     BadFenFormat(&'static str),
+    TooManyRanks,
+    TooFewRanks,
+    RankTooLong(u32),
+    RankTooShort(u32),
+    MissingKing(Side),
+    TooManyKings(Side),
+    PawnOnBackRank(Square),
+    InvalidCastlingRights(Side),
+    InvalidEnPassantSquare(Square),
+    InvalidMoveCounter(FenField),
+    // the side that is not to move is in check, its king could be captured
+    OpponentInCheck(Side),
 }
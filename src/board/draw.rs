@@ -0,0 +1,152 @@
+use crate::{
+    bitboard::Bitboard,
+    defs::{Piece, Side},
+};
+
+use super::Board;
+
+// a1 is a dark square
+const DARK_SQUARES: u64 = 0xAA55AA55AA55AA55;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DrawReason {
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+}
+
+impl Board {
+    // automatic draws are reported before the ones a player has to claim
+    pub fn draw_reason(&self) -> Option<DrawReason> {
+        let repetitions = self.get_repetition_count();
+
+        if repetitions >= 5 {
+            Some(DrawReason::FivefoldRepetition)
+        } else if self.state.halfmove_clock >= 150 {
+            Some(DrawReason::SeventyFiveMoveRule)
+        } else if self.has_insufficient_material() {
+            Some(DrawReason::InsufficientMaterial)
+        } else if repetitions >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if self.state.halfmove_clock >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.draw_reason().is_some()
+    }
+
+    // number of times the current position occurred, itself included.
+    // positions are compared through their hash, which also covers castling rights and en passant
+    pub fn get_repetition_count(&self) -> usize {
+        // a pawn move or a capture can not be undone, nothing before it can repeat
+        let window = (self.state.halfmove_clock as usize).min(self.history.len());
+
+        1 + self
+            .history
+            .iter()
+            .rev()
+            .take(window)
+            .skip(1)
+            .step_by(2)
+            .filter(|state| state.hash == self.state.hash)
+            .count()
+    }
+
+    // no sequence of legal moves can lead to a checkmate
+    pub fn has_insufficient_material(&self) -> bool {
+        let bitboards = &self.state.bitboards;
+        let mut bishops = Bitboard(0);
+        let mut knights = Bitboard(0);
+
+        for side in [Side::White, Side::Black] {
+            let pieces = &bitboards[side as usize];
+            if pieces[Piece::Queen as usize] != 0
+                || pieces[Piece::Rook as usize] != 0
+                || pieces[Piece::Pawn as usize] != 0
+            {
+                return false;
+            }
+            bishops |= pieces[Piece::Bishop as usize];
+            knights |= pieces[Piece::Knight as usize];
+        }
+
+        let minor_pieces = bishops.count_occupied_squares() + knights.count_occupied_squares();
+        // a lone minor piece, or bishops which all stand on the same square color
+        minor_pieces <= 1
+            || (knights == 0 && (bishops & DARK_SQUARES == 0 || bishops & !DARK_SQUARES == 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::communication::uci::parse_uci_position;
+
+    #[test]
+    fn move_rule_tests() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+        assert_eq!(board.draw_reason(), None);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+        assert_eq!(board.draw_reason(), Some(DrawReason::FiftyMoveRule));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 150 105").unwrap();
+        assert_eq!(board.draw_reason(), Some(DrawReason::SeventyFiveMoveRule));
+    }
+
+    #[test]
+    fn repetition_tests() {
+        let shuffle = " g1f3 g8f6 f3g1 f6g8";
+        let board = parse_uci_position(&format!("position startpos moves{}", shuffle)).unwrap();
+        assert_eq!(board.get_repetition_count(), 2);
+        assert!(!board.is_draw());
+
+        let board =
+            parse_uci_position(&format!("position startpos moves{}", shuffle.repeat(2))).unwrap();
+        assert_eq!(board.get_repetition_count(), 3);
+        assert_eq!(board.draw_reason(), Some(DrawReason::ThreefoldRepetition));
+
+        let board =
+            parse_uci_position(&format!("position startpos moves{}", shuffle.repeat(4))).unwrap();
+        assert_eq!(board.get_repetition_count(), 5);
+        assert_eq!(board.draw_reason(), Some(DrawReason::FivefoldRepetition));
+
+        // the positions are the same but black lost its castling rights on the way
+        let board =
+            parse_uci_position("position startpos moves g1f3 g8f6 f3g1 h8g8 g1f3 g8h8 f3g1 f6g8")
+                .unwrap();
+        assert_eq!(board.get_repetition_count(), 1);
+    }
+
+    #[test]
+    fn insufficient_material_tests() {
+        let fens = [
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1N2K3 b - - 0 1",
+            "2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+            "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        ];
+        for fen in fens.iter() {
+            let board = Board::from_fen(fen).unwrap();
+            assert_eq!(board.draw_reason(), Some(DrawReason::InsufficientMaterial));
+        }
+
+        let fens = [
+            Board::default().to_fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1".to_string(),
+            "4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1".to_string(),
+            "4kb2/8/8/8/8/8/8/1N2K3 w - - 0 1".to_string(),
+            "2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1".to_string(),
+        ];
+        for fen in fens.iter() {
+            assert!(!Board::from_fen(fen).unwrap().is_draw());
+        }
+    }
+}
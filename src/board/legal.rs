@@ -0,0 +1,234 @@
+use crate::{
+    bitboard::Bitboard,
+    defs::{Piece, Side, Square},
+    move_generator::{
+        movelist::{Move, MoveList},
+        MoveGenerator,
+    },
+};
+
+use super::Board;
+
+// what makes a pseudo legal move illegal, computed once per position
+struct Restrictions {
+    king_square: Square,
+    checkers: Bitboard,
+    // squares a non king move must land on to block or capture a single checker
+    evasion_mask: Bitboard,
+    pinned: Bitboard,
+    // for each pinned piece, the squares between the king and the pinner, pinner included
+    pin_rays: [Bitboard; 64],
+}
+
+impl Board {
+    pub fn generate_legal_moves(&self) -> MoveList {
+        let restrictions = self.get_restrictions();
+        let mut movelist = MoveList::new();
+
+        for mv in self.generate_moves().into_iter() {
+            if self.is_legal(mv, &restrictions) {
+                movelist.add_move(mv);
+            }
+        }
+        movelist
+    }
+
+    fn get_restrictions(&self) -> Restrictions {
+        let side = self.state.side_to_move;
+        let opposite = side.get_opposite_side();
+        let occupancy = self.state.occupancies[2];
+        let king_square = self.state.bitboards[side as usize][Piece::King as usize]
+            .get_ls1b_index()
+            .unwrap_or_default();
+
+        let checkers = self.get_attackers(king_square, opposite, occupancy);
+        let evasion_mask = match checkers.count_occupied_squares() {
+            0 => Bitboard(u64::MAX),
+            1 => {
+                let checker = checkers.get_ls1b_index().unwrap_or_default();
+                checkers | self.get_squares_between(king_square, checker)
+            }
+            // in double check only the king can move
+            _ => Bitboard(0),
+        };
+
+        // enemy sliders seeing the king through our own pieces
+        let enemies = self.state.bitboards[opposite as usize];
+        let queens = enemies[Piece::Queen as usize];
+        let pinners = (MoveGenerator::instance()
+            .get_rook_attacks(king_square, self.state.occupancies[opposite as usize])
+            & (enemies[Piece::Rook as usize] | queens))
+            | (MoveGenerator::instance()
+                .get_bishop_attacks(king_square, self.state.occupancies[opposite as usize])
+                & (enemies[Piece::Bishop as usize] | queens));
+
+        let mut pinned = Bitboard(0);
+        let mut pin_rays = [Bitboard(0); 64];
+        for pinner in pinners.into_iter() {
+            let ray = self.get_squares_between(king_square, pinner);
+            let blockers = ray & occupancy;
+            if blockers.count_occupied_squares() == 1 {
+                if let Some(square) = blockers.get_ls1b_index() {
+                    pinned.set_square(square);
+                    pin_rays[square as usize] = ray | Bitboard::from_square(pinner);
+                }
+            }
+        }
+
+        Restrictions {
+            king_square,
+            checkers,
+            evasion_mask,
+            pinned,
+            pin_rays,
+        }
+    }
+
+    fn is_legal(&self, mv: Move, restrictions: &Restrictions) -> bool {
+        let side = self.state.side_to_move;
+        let opposite = side.get_opposite_side();
+        let source = mv.get_source_square();
+        let target = mv.get_target_square();
+
+        if mv.get_piece() == Piece::King {
+            // the castling generator already checked the king and the square it crosses
+            if mv.get_castling() {
+                return restrictions.checkers == 0 && !self.is_square_attacked(target, opposite);
+            }
+            // the king must not hide behind itself from a slider
+            let occupancy = self.state.occupancies[2] & !Bitboard::from_square(source);
+            return self.get_attackers(target, opposite, occupancy) == 0;
+        }
+
+        if mv.get_en_passant() {
+            return self.is_legal_en_passant(mv, restrictions);
+        }
+
+        if restrictions.evasion_mask & Bitboard::from_square(target) == 0 {
+            return false;
+        }
+
+        !restrictions.pinned.get_square(source)
+            || restrictions.pin_rays[source as usize].get_square(target)
+    }
+
+    // en passant removes two pieces from the same rank, so it is simpler to replay it
+    fn is_legal_en_passant(&self, mv: Move, restrictions: &Restrictions) -> bool {
+        let opposite = self.state.side_to_move.get_opposite_side();
+        let captured = match self.state.side_to_move {
+            Side::White => mv.get_target_square() - 8,
+            Side::Black => mv.get_target_square() + 8,
+        };
+        let occupancy = (self.state.occupancies[2]
+            & !(Bitboard::from_square(mv.get_source_square()) | Bitboard::from_square(captured)))
+            | Bitboard::from_square(mv.get_target_square());
+
+        let enemies = self.state.bitboards[opposite as usize];
+        let queens = enemies[Piece::Queen as usize];
+        let sliders = (MoveGenerator::instance()
+            .get_rook_attacks(restrictions.king_square, occupancy)
+            & (enemies[Piece::Rook as usize] | queens))
+            | (MoveGenerator::instance().get_bishop_attacks(restrictions.king_square, occupancy)
+                & (enemies[Piece::Bishop as usize] | queens));
+        let leapers = restrictions.checkers
+            & (enemies[Piece::Knight as usize] | enemies[Piece::Pawn as usize])
+            & !Bitboard::from_square(captured);
+
+        sliders == 0 && leapers == 0
+    }
+
+    // pieces of `side` attacking `square` given the occupancy
+    pub(super) fn get_attackers(
+        &self,
+        square: Square,
+        side: Side,
+        occupancy: Bitboard,
+    ) -> Bitboard {
+        let pieces = self.state.bitboards[side as usize];
+        let queens = pieces[Piece::Queen as usize];
+
+        (MoveGenerator::instance().get_pawn_attacks(square, side.get_opposite_side())
+            & pieces[Piece::Pawn as usize])
+            | (MoveGenerator::instance().get_knight_attacks(square)
+                & pieces[Piece::Knight as usize])
+            | (MoveGenerator::instance().get_king_attacks(square) & pieces[Piece::King as usize])
+            | (MoveGenerator::instance().get_bishop_attacks(square, occupancy)
+                & (pieces[Piece::Bishop as usize] | queens))
+            | (MoveGenerator::instance().get_rook_attacks(square, occupancy)
+                & (pieces[Piece::Rook as usize] | queens))
+    }
+
+    // empty when both squares are not on the same line
+    fn get_squares_between(&self, from: Square, to: Square) -> Bitboard {
+        let from_bitboard = Bitboard::from_square(from);
+        let to_bitboard = Bitboard::from_square(to);
+
+        let rook_from = MoveGenerator::instance().get_rook_attacks(from, to_bitboard);
+        if rook_from.get_square(to) {
+            return rook_from & MoveGenerator::instance().get_rook_attacks(to, from_bitboard);
+        }
+        let bishop_from = MoveGenerator::instance().get_bishop_attacks(from, to_bitboard);
+        if bishop_from.get_square(to) {
+            return bishop_from & MoveGenerator::instance().get_bishop_attacks(to, from_bitboard);
+        }
+        Bitboard(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    // every pseudo legal move that make_move accepts must be generated, and nothing else
+    fn assert_same_as_make_move(fen: &str) {
+        let mut board = Board::from_fen(fen).unwrap();
+        let legal_moves = board.generate_legal_moves();
+        let mut count = 0;
+        for mv in board.generate_moves().into_iter() {
+            if board.make_move(mv, false).is_ok() {
+                board.take_back_move();
+                assert!(legal_moves.contains(mv), "{} missing in {}", mv, fen);
+                count += 1;
+            }
+        }
+        assert_eq!(legal_moves.len(), count, "{}", fen);
+    }
+
+    #[test]
+    fn generate_legal_moves_tests() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            // double check
+            "4r2k/8/8/8/8/3n4/8/4K3 w - - 0 1",
+            // castling out of check
+            "4r2k/8/8/8/8/8/3B4/R3K3 w Q - 0 1",
+            // the en passant capture would expose the king along the rank
+            "8/8/8/K2pP2r/8/8/8/7k w - d6 0 1",
+        ];
+        for fen in fens.iter() {
+            assert_same_as_make_move(fen);
+        }
+    }
+
+    #[test]
+    fn pinned_piece_tests() {
+        // the knight is pinned and can not move, the rook can only move along the pin
+        let board = Board::from_fen("4r2k/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        let moves = board.generate_legal_moves();
+        assert!(moves.into_iter().all(|mv| mv.get_piece() != Piece::Knight));
+
+        let board = Board::from_fen("4r2k/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+        let rook_moves: Vec<Move> = board
+            .generate_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.get_piece() == Piece::Rook)
+            .collect();
+        assert_eq!(rook_moves.len(), 6);
+        #[rustfmt::skip]
+        assert!(rook_moves.contains(&Move::new(E2, E8, Piece::Rook, None, true, false, false, false)));
+    }
+}
@@ -2,11 +2,11 @@ use std::hint::unreachable_unchecked;
 
 use crate::{
     constants::*,
-    defs::{Piece, Side},
+    defs::{Piece, Side, Square},
     move_generator::movelist::Move,
 };
 
-use super::Board;
+use super::{zobrist::ZOBRIST_KEYS, Board};
 
 #[derive(Debug)]
 pub enum MakeMoveError {
@@ -15,49 +15,52 @@ pub enum MakeMoveError {
 }
 
 impl Board {
+    // with `only_capture` the move must be one generate_captures returns, a promotion counts
     pub fn make_move(&mut self, mv: Move, only_capture: bool) -> Result<(), MakeMoveError> {
-        if mv.get_capture() && only_capture {
+        if only_capture && !mv.get_capture() && mv.get_promotion().is_none() {
             return Err(MakeMoveError::NotACapture);
         }
         self.store_state();
 
+        let side = self.state.side_to_move;
+        let opposite = side.get_opposite_side();
+
+        // castling rights and en passant square are hashed back in once updated
+        self.state.hash ^= ZOBRIST_KEYS.castling(&self.state);
+        self.state.hash ^= ZOBRIST_KEYS.en_passant(self.state.en_passant_square);
+
         // clear source square and set target square on the correct bitboard
-        self.state.bitboards[self.state.side_to_move as usize][mv.get_piece() as usize]
-            .clear_square(mv.get_source_square());
-        self.state.bitboards[self.state.side_to_move as usize][mv.get_piece() as usize]
-            .set_square(mv.get_target_square());
+        self.remove_piece(side, mv.get_piece(), mv.get_source_square());
 
         // clear target square on opposite bitboard if move is a capture
         if mv.get_capture() {
-            for bitboard in self.state.bitboards
-                [self.state.side_to_move.get_opposite_side() as usize]
+            for (piece, bitboard) in self.state.bitboards[opposite as usize]
                 .iter_mut()
+                .enumerate()
             {
-                bitboard.clear_square(mv.get_target_square());
+                if bitboard.get_square(mv.get_target_square()) {
+                    bitboard.clear_square(mv.get_target_square());
+                    self.state.hash ^= ZOBRIST_KEYS.piece(opposite, piece, mv.get_target_square());
+                }
             }
         }
 
-        // handle promotion
+        // handle promotion, the pawn is replaced by the promoted piece on the target square
         match mv.get_promotion() {
-            Some(promotion) => {
-                // clear pawn from target square
-                self.state.bitboards[self.state.side_to_move as usize][mv.get_piece() as usize]
-                    .clear_square(mv.get_target_square());
-                // add new bit on the correct piece bitboard
-                self.state.bitboards[self.state.side_to_move as usize][promotion as usize]
-                    .set_square(mv.get_target_square())
-            }
-            None => {}
+            Some(promotion) => self.add_piece(side, Piece::from(promotion), mv.get_target_square()),
+            None => self.add_piece(side, mv.get_piece(), mv.get_target_square()),
         }
 
         // handle en passant
         if mv.get_en_passant() {
             // clear the correct pawn bit
-            match self.state.side_to_move {
-                Side::White => self.state.bitboards[Side::Black as usize][Piece::Pawn as usize]
-                    .clear_square(mv.get_target_square() - 8),
-                Side::Black => self.state.bitboards[Side::White as usize][Piece::Pawn as usize]
-                    .clear_square(mv.get_target_square() + 8),
+            match side {
+                Side::White => {
+                    self.remove_piece(Side::Black, Piece::Pawn, mv.get_target_square() - 8)
+                }
+                Side::Black => {
+                    self.remove_piece(Side::White, Piece::Pawn, mv.get_target_square() + 8)
+                }
             };
         }
         // in any case, reset en passant square
@@ -65,7 +68,7 @@ impl Board {
 
         // in case of double push, set an en passant target square
         if mv.get_double_push() {
-            match self.state.side_to_move {
+            match side {
                 Side::White => self.state.en_passant_square = Some(mv.get_target_square() - 8),
                 Side::Black => self.state.en_passant_square = Some(mv.get_target_square() + 8),
             };
@@ -73,38 +76,33 @@ impl Board {
 
         if mv.get_castling() {
             match mv.get_target_square() {
-                G1 => {
-                    self.state.bitboards[Side::White as usize][Piece::Rook as usize]
-                        .clear_square(H1);
-                    self.state.bitboards[Side::White as usize][Piece::Rook as usize].set_square(F1);
-                }
-                C1 => {
-                    self.state.bitboards[Side::White as usize][Piece::Rook as usize]
-                        .clear_square(A1);
-                    self.state.bitboards[Side::White as usize][Piece::Rook as usize].set_square(D1);
-                }
-                G8 => {
-                    self.state.bitboards[Side::Black as usize][Piece::Rook as usize]
-                        .clear_square(H8);
-                    self.state.bitboards[Side::Black as usize][Piece::Rook as usize].set_square(F8);
-                }
-                C8 => {
-                    self.state.bitboards[Side::Black as usize][Piece::Rook as usize]
-                        .clear_square(A8);
-                    self.state.bitboards[Side::Black as usize][Piece::Rook as usize].set_square(D8);
-                }
+                G1 => self.move_piece(Side::White, Piece::Rook, H1, F1),
+                C1 => self.move_piece(Side::White, Piece::Rook, A1, D1),
+                G8 => self.move_piece(Side::Black, Piece::Rook, H8, F8),
+                C8 => self.move_piece(Side::Black, Piece::Rook, A8, D8),
                 _ => unsafe { unreachable_unchecked() },
             }
         }
-        let side = self.state.side_to_move as usize;
-        let opposite = self.state.side_to_move.get_opposite_side() as usize;
+
+        // the halfmove clock is reset by any irreversible move
+        match mv.get_piece() == Piece::Pawn || mv.get_capture() {
+            true => self.state.halfmove_clock = 0,
+            false => self.state.halfmove_clock += 1,
+        }
+        if side == Side::Black {
+            self.state.fullmove_number += 1;
+        }
 
         // update castle rights
-        self.state.castling_rights[side] = self.state.castling_rights[side]
-            & CASTLING_RIGHTS_UPDATE_TABLE[side][mv.get_source_square() as usize];
+        self.state.castling_rights[side as usize] = self.state.castling_rights[side as usize]
+            & CASTLING_RIGHTS_UPDATE_TABLE[side as usize][mv.get_source_square() as usize];
+
+        self.state.castling_rights[opposite as usize] = self.state.castling_rights
+            [opposite as usize]
+            & CASTLING_RIGHTS_UPDATE_TABLE[opposite as usize][mv.get_target_square() as usize];
 
-        self.state.castling_rights[opposite] = self.state.castling_rights[opposite]
-            & CASTLING_RIGHTS_UPDATE_TABLE[opposite][mv.get_target_square() as usize];
+        self.state.hash ^= ZOBRIST_KEYS.castling(&self.state);
+        self.state.hash ^= ZOBRIST_KEYS.en_passant(self.state.en_passant_square);
 
         // update occupancies
         self.state.occupancies = Board::compute_occupancies(self.state.bitboards);
@@ -112,16 +110,40 @@ impl Board {
         // if King on side to move is in check rollback
 
         if self.is_square_attacked(
-            self.state.bitboards[self.state.side_to_move as usize][Piece::King as usize]
+            self.state.bitboards[side as usize][Piece::King as usize]
                 .get_ls1b_index()
                 .unwrap(),
-            self.state.side_to_move.get_opposite_side(),
+            opposite,
         ) {
             self.take_back_move();
             return Err(MakeMoveError::IllegalMove(mv));
         };
 
-        self.state.side_to_move = self.state.side_to_move.get_opposite_side();
+        self.state.side_to_move = opposite;
+        self.state.hash ^= ZOBRIST_KEYS.side();
+
+        debug_assert_eq!(
+            self.state.hash,
+            Board::compute_hash(&self.state),
+            "incremental hash diverged after {}",
+            mv
+        );
+
         Ok(())
     }
+
+    fn add_piece(&mut self, side: Side, piece: Piece, square: Square) {
+        self.state.bitboards[side as usize][piece as usize].set_square(square);
+        self.state.hash ^= ZOBRIST_KEYS.piece(side, piece as usize, square);
+    }
+
+    fn remove_piece(&mut self, side: Side, piece: Piece, square: Square) {
+        self.state.bitboards[side as usize][piece as usize].clear_square(square);
+        self.state.hash ^= ZOBRIST_KEYS.piece(side, piece as usize, square);
+    }
+
+    fn move_piece(&mut self, side: Side, piece: Piece, source: Square, target: Square) {
+        self.remove_piece(side, piece, source);
+        self.add_piece(side, piece, target);
+    }
 }
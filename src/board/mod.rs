@@ -1,26 +1,35 @@
 mod attacks;
 mod constants;
 mod defs;
+mod draw;
+mod legal;
 mod make;
-mod search;
+mod outcome;
+mod san;
+mod see;
+mod zobrist;
 
 use std::{convert::TryFrom, fmt};
 
 use crate::{
     bitboard::Bitboard,
-    constants::{SQUARE_NAME, UNICODE_PIECE},
+    constants::{A1, A8, ASCII_PIECE, E1, E8, H1, H8, RANK_1, RANK_8, SQUARE_NAME, UNICODE_PIECE},
     defs::{CastleRights, Piece, Side, Square},
-    move_generator::MoveGenerator,
 };
 
 use self::{
     constants::{
         EMPTY_POSITION, INITIAL_BLACK_POSITIONS, INITIAL_OCCUPANCIES, INITIAL_WHITE_POSITIONS,
     },
-    defs::ParseFenError,
+    defs::FenField,
 };
 
+pub use defs::ParseFenError;
+pub use draw::DrawReason;
 pub use make::MakeMoveError;
+pub use outcome::Outcome;
+pub use san::ParseSanError;
+pub use see::SEE_VALUES;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BoardState {
@@ -29,15 +38,17 @@ pub struct BoardState {
     pub side_to_move: Side,
     pub en_passant_square: Option<Square>,
     pub castling_rights: [CastleRights; 2],
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+    pub hash: u64,
 }
 
 pub type BoardHistory = Vec<BoardState>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Board {
     state: BoardState,
     history: BoardHistory,
-    move_generator: MoveGenerator,
 }
 
 impl fmt::Display for Board {
@@ -69,7 +80,7 @@ impl fmt::Display for Board {
                     for (piece, bitboard) in bitboards.iter().enumerate() {
                         if bitboard.get_square(square) {
                             occupied = true;
-                            write!(f, "{} ", UNICODE_PIECE[side as usize][piece as usize])?;
+                            write!(f, "{} ", UNICODE_PIECE[side][piece])?;
                             break;
                         }
                     }
@@ -88,6 +99,7 @@ impl fmt::Display for Board {
         writeln!(f, "side to move : {}", side_to_move)?;
         writeln!(f, "en passant square : {}", en_passant_square)?;
         writeln!(f, "castling rights : {}", castling_rights)?;
+        writeln!(f, "hash key : {:#018x}", self.state.hash)?;
         Ok(())
     }
 }
@@ -96,79 +108,192 @@ impl TryFrom<Vec<&str>> for Board {
     type Error = ParseFenError;
 
     fn try_from(value: Vec<&str>) -> Result<Self, Self::Error> {
-        match value.len() >= 4 {
-            false => return Err(ParseFenError::BadFenFormat("wrong number of args")),
-            true => {}
-        };
+        if value.len() < 4 || value.len() > 6 {
+            return Err(ParseFenError::BadFenFormat("wrong number of args"));
+        }
 
         let bitboards = Self::parse_fen_positions(value[0])?;
         let side_to_move = Self::parse_fen_side_to_move(value[1])?;
         let castling_rights = Self::parse_fen_castling_rights(value[2])?;
         let en_passant_square = Self::parse_fen_en_passant_square(value[3])?;
+        // move counters are optional, as in EPD strings
+        let halfmove_clock = match value.get(4) {
+            Some(fen_str) => Self::parse_fen_move_counter(fen_str, FenField::HalfmoveClock)?,
+            None => 0,
+        };
+        // the fullmove number starts at 1, a 0 could not be written back as given
+        let fullmove_number = match value.get(5) {
+            Some(fen_str) => match Self::parse_fen_move_counter(fen_str, FenField::FullmoveNumber)?
+            {
+                0 => return Err(ParseFenError::InvalidMoveCounter(FenField::FullmoveNumber)),
+                fullmove_number => fullmove_number,
+            },
+            None => 1,
+        };
+
+        let mut state = BoardState {
+            bitboards,
+            occupancies: Self::compute_occupancies(bitboards),
+            side_to_move,
+            en_passant_square,
+            castling_rights,
+            halfmove_clock,
+            fullmove_number,
+            hash: 0,
+        };
+        Self::validate_fen_state(&state)?;
+        state.hash = Self::compute_hash(&state);
 
         Ok(Self {
-            state: BoardState {
-                bitboards: bitboards,
-                occupancies: Self::compute_occupancies(bitboards),
-                side_to_move: side_to_move,
-                en_passant_square: en_passant_square,
-                castling_rights: castling_rights,
-            },
+            state,
             history: vec![],
-            move_generator: MoveGenerator::new(),
         })
     }
 }
 
 impl PartialEq for Board {
     fn eq(&self, other: &Self) -> bool {
-        println!("{:?}", self.history);
-        println!("{:?}", other.history);
         (self.state == other.state) && (self.history == other.history)
     }
 }
 
-impl Board {
-    pub fn default() -> Self {
+impl Default for Board {
+    fn default() -> Self {
+        let mut state = BoardState {
+            bitboards: [INITIAL_WHITE_POSITIONS, INITIAL_BLACK_POSITIONS],
+            side_to_move: Side::White,
+            occupancies: INITIAL_OCCUPANCIES,
+            en_passant_square: None,
+            castling_rights: [CastleRights::Both; 2],
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            hash: 0,
+        };
+        state.hash = Self::compute_hash(&state);
+
         Self {
-            state: BoardState {
-                bitboards: [INITIAL_WHITE_POSITIONS, INITIAL_BLACK_POSITIONS],
-                side_to_move: Side::White,
-                occupancies: INITIAL_OCCUPANCIES,
-                en_passant_square: None,
-                castling_rights: [CastleRights::Both; 2],
-            },
+            state,
             history: vec![],
-            move_generator: MoveGenerator::new(),
         }
     }
+}
+
+impl Board {
+    pub fn get_state(&self) -> &BoardState {
+        &self.state
+    }
 
     pub fn from_fen(fen_string: &str) -> Result<Self, ParseFenError> {
         // first block is in regard to piece placement, it start from rank 8 all the way to rank 1
-        let fen_parts: Vec<&str> = fen_string.trim().split(' ').collect();
+        let fen_parts: Vec<&str> = fen_string.split_whitespace().collect();
         Self::try_from(fen_parts)
     }
 
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty_squares = 0;
+            for file in 0..8 {
+                let square = rank * 8 + file;
+                match self.get_piece_on(square) {
+                    Some((side, piece)) => {
+                        if empty_squares > 0 {
+                            fen.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+                        fen.push_str(ASCII_PIECE[side as usize][piece as usize]);
+                    }
+                    None => empty_squares += 1,
+                }
+            }
+            if empty_squares > 0 {
+                fen.push_str(&empty_squares.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(match self.state.side_to_move {
+            Side::White => " w ",
+            Side::Black => " b ",
+        });
+
+        let castling_rights = [
+            (Side::White, CastleRights::KingSide, 'K'),
+            (Side::White, CastleRights::QueenSide, 'Q'),
+            (Side::Black, CastleRights::KingSide, 'k'),
+            (Side::Black, CastleRights::QueenSide, 'q'),
+        ];
+        let mut can_castle = false;
+        for (side, castle, char) in castling_rights.iter() {
+            if self.state.castling_rights[*side as usize] & castle.to_index() != CastleRights::None
+            {
+                fen.push(*char);
+                can_castle = true;
+            }
+        }
+        if !can_castle {
+            fen.push('-');
+        }
+
+        match self.state.en_passant_square {
+            Some(square) => fen.push_str(&format!(" {} ", SQUARE_NAME[square as usize])),
+            None => fen.push_str(" - "),
+        }
+
+        fen.push_str(&format!(
+            "{} {}",
+            self.state.halfmove_clock, self.state.fullmove_number
+        ));
+
+        fen
+    }
+
+    pub fn get_piece_on(&self, square: Square) -> Option<(Side, Piece)> {
+        for side in [Side::White, Side::Black] {
+            for (piece, bitboard) in self.state.bitboards[side as usize].iter().enumerate() {
+                if bitboard.get_square(square) {
+                    return Piece::try_from(piece as u32)
+                        .ok()
+                        .map(|piece| (side, piece));
+                }
+            }
+        }
+        None
+    }
+
     fn parse_fen_positions(fen_position: &str) -> Result<[[Bitboard; 6]; 2], ParseFenError> {
         let mut bitboards = [EMPTY_POSITION, EMPTY_POSITION];
 
         let mut rank: u32 = 7;
         let mut file: u32 = 0;
 
-        for char in fen_position.chars() {
+        for (index, char) in fen_position.chars().enumerate() {
+            // nothing can be placed past the h file
+            if file >= 8 && char != '/' {
+                return Err(ParseFenError::RankTooLong(rank + 1));
+            }
             let square = rank * 8 + file;
 
             match char {
                 '/' => {
+                    if file < 8 {
+                        return Err(ParseFenError::RankTooShort(rank + 1));
+                    }
+                    if rank == 0 {
+                        return Err(ParseFenError::TooManyRanks);
+                    }
                     file = 0;
                     rank -= 1;
                 }
                 '1'..='8' => match char.to_digit(10) {
-                    Some(incr) => {
+                    Some(incr) if file + incr <= 8 => {
                         file += incr;
                     }
-                    None => {
-                        return Err(ParseFenError::UnexpectedChar);
+                    _ => {
+                        return Err(ParseFenError::RankTooLong(rank + 1));
                     }
                 },
                 'K' => {
@@ -219,10 +344,23 @@ impl Board {
                     bitboards[Side::Black as usize][Piece::Rook as usize].set_square(square);
                     file += 1;
                 }
-                _ => return Err(ParseFenError::UnexpectedChar),
+                _ => {
+                    return Err(ParseFenError::UnexpectedChar {
+                        field: FenField::Placement,
+                        char,
+                        index,
+                    })
+                }
             }
         }
 
+        if rank > 0 {
+            return Err(ParseFenError::TooFewRanks);
+        }
+        if file < 8 {
+            return Err(ParseFenError::RankTooShort(1));
+        }
+
         Ok(bitboards)
     }
 
@@ -237,55 +375,155 @@ impl Board {
     }
 
     fn parse_fen_side_to_move(fen_str: &str) -> Result<Side, ParseFenError> {
-        return match fen_str.chars().nth(0) {
-            Some(char) => match char {
-                'w' => Ok(Side::White),
-                'b' => Ok(Side::Black),
-                _ => Err(ParseFenError::UnexpectedChar),
-            },
-            None => Err(ParseFenError::EmptyString),
+        let mut chars = fen_str.chars().enumerate();
+        let side = match chars.next() {
+            Some((_, 'w')) => Side::White,
+            Some((_, 'b')) => Side::Black,
+            Some((index, char)) => {
+                return Err(Self::unexpected_char(FenField::SideToMove, char, index))
+            }
+            None => return Err(ParseFenError::EmptyString),
         };
+        match chars.next() {
+            Some((index, char)) => Err(Self::unexpected_char(FenField::SideToMove, char, index)),
+            None => Ok(side),
+        }
     }
 
     fn parse_fen_castling_rights(fen_str: &str) -> Result<[CastleRights; 2], ParseFenError> {
         let mut castling_rights = [CastleRights::None; 2];
-        for char in fen_str.chars() {
+        match fen_str {
+            "" => return Err(ParseFenError::EmptyString),
+            "-" => return Ok(castling_rights),
+            _ => {}
+        }
+        for (index, char) in fen_str.chars().enumerate() {
             match char {
-                '-' => return Ok(castling_rights),
                 'K' => castling_rights[0] = castling_rights[0].add(CastleRights::KingSide),
                 'k' => castling_rights[1] = castling_rights[1].add(CastleRights::KingSide),
                 'Q' => castling_rights[0] = castling_rights[0].add(CastleRights::QueenSide),
                 'q' => castling_rights[1] = castling_rights[1].add(CastleRights::QueenSide),
-                _ => return Err(ParseFenError::UnexpectedChar),
+                _ => return Err(Self::unexpected_char(FenField::CastlingRights, char, index)),
             };
         }
         Ok(castling_rights)
     }
 
     fn parse_fen_en_passant_square(fen_str: &str) -> Result<Option<Square>, ParseFenError> {
-        let mut rank = 0;
-        let mut file = 0;
-        for char in fen_str.chars() {
-            match char {
-                '-' => return Ok(None),
-                'a'..='h' => {
-                    file = char as u32 - 97;
-                }
-                '3' | '6' => rank = char.to_digit(10).unwrap() - 1,
-                _ => return Err(ParseFenError::UnexpectedChar),
+        if fen_str == "-" {
+            return Ok(None);
+        }
+        let mut chars = fen_str.chars().enumerate();
+        let file = match chars.next() {
+            Some((_, char @ 'a'..='h')) => char as u32 - 97,
+            Some((index, char)) => {
+                return Err(Self::unexpected_char(
+                    FenField::EnPassantSquare,
+                    char,
+                    index,
+                ))
+            }
+            None => return Err(ParseFenError::EmptyString),
+        };
+        // a pawn that just moved two squares can only be passed on the third or sixth rank
+        let rank = match chars.next() {
+            Some((_, char @ ('3' | '6'))) => char as u32 - 49,
+            Some((index, char)) => {
+                return Err(Self::unexpected_char(
+                    FenField::EnPassantSquare,
+                    char,
+                    index,
+                ))
+            }
+            None => return Err(ParseFenError::BadFenFormat("en passant square has no rank")),
+        };
+        match chars.next() {
+            Some((index, char)) => Err(Self::unexpected_char(
+                FenField::EnPassantSquare,
+                char,
+                index,
+            )),
+            None => Ok(Some(rank * 8 + file)),
+        }
+    }
+
+    fn parse_fen_move_counter(fen_str: &str, field: FenField) -> Result<u32, ParseFenError> {
+        fen_str
+            .parse::<u32>()
+            .map_err(|_| ParseFenError::InvalidMoveCounter(field))
+    }
+
+    fn unexpected_char(field: FenField, char: char, index: usize) -> ParseFenError {
+        ParseFenError::UnexpectedChar { field, char, index }
+    }
+
+    // rejects the setups every field parsed fine on its own but which can not happen in a game
+    fn validate_fen_state(state: &BoardState) -> Result<(), ParseFenError> {
+        // king, king side rook and queen side rook initial squares
+        let castling_squares = [(E1, H1, A1), (E8, H8, A8)];
+
+        for side in [Side::White, Side::Black] {
+            let bitboards = state.bitboards[side as usize];
+            match bitboards[Piece::King as usize].count_occupied_squares() {
+                0 => return Err(ParseFenError::MissingKing(side)),
+                1 => {}
+                _ => return Err(ParseFenError::TooManyKings(side)),
+            }
+
+            let (king, king_side_rook, queen_side_rook) = castling_squares[side as usize];
+            let rights = state.castling_rights[side as usize];
+            let king_home = bitboards[Piece::King as usize].get_square(king);
+            let rooks = bitboards[Piece::Rook as usize];
+            if (rights & CastleRights::KingSide.to_index() != CastleRights::None
+                && !(king_home && rooks.get_square(king_side_rook)))
+                || (rights & CastleRights::QueenSide.to_index() != CastleRights::None
+                    && !(king_home && rooks.get_square(queen_side_rook)))
+            {
+                return Err(ParseFenError::InvalidCastlingRights(side));
+            }
+        }
+
+        let pawns = state.bitboards[Side::White as usize][Piece::Pawn as usize]
+            | state.bitboards[Side::Black as usize][Piece::Pawn as usize];
+        if let Some(square) = (pawns & (RANK_1 | RANK_8)).get_ls1b_index() {
+            return Err(ParseFenError::PawnOnBackRank(square));
+        }
+
+        if let Some(square) = state.en_passant_square {
+            // the pawn that moved must stand in front of the square it passed, which is left empty
+            // along with the square it came from
+            let (pawn_square, origin_square, expected_rank) = match state.side_to_move {
+                Side::White => (square - 8, square + 8, 5),
+                Side::Black => (square + 8, square - 8, 2),
             };
+            let opponent = state.side_to_move.get_opposite_side();
+            if square / 8 != expected_rank
+                || !state.bitboards[opponent as usize][Piece::Pawn as usize].get_square(pawn_square)
+                || state.occupancies[2].get_square(square)
+                || state.occupancies[2].get_square(origin_square)
+            {
+                return Err(ParseFenError::InvalidEnPassantSquare(square));
+            }
+        }
+
+        let opponent = state.side_to_move.get_opposite_side();
+        let board = Board {
+            state: *state,
+            history: vec![],
+        };
+        if let Some(king_square) =
+            state.bitboards[opponent as usize][Piece::King as usize].get_ls1b_index()
+        {
+            if board.is_square_attacked(king_square, state.side_to_move) {
+                return Err(ParseFenError::OpponentInCheck(opponent));
+            }
         }
-        Ok(Some(rank * 8 + file))
+
+        Ok(())
     }
 
     fn store_state(&mut self) {
-        self.history.push(BoardState {
-            bitboards: self.state.bitboards,
-            occupancies: self.state.occupancies,
-            side_to_move: self.state.side_to_move,
-            en_passant_square: self.state.en_passant_square,
-            castling_rights: self.state.castling_rights,
-        });
+        self.history.push(self.state);
     }
     pub fn take_back_move(&mut self) {
         self.state = self.history.pop().unwrap_or(self.state);
@@ -295,6 +533,10 @@ impl Board {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        communication::uci::parse_uci_position,
+        constants::{D8, E3},
+    };
 
     #[test]
     fn parse_fen_positions_tests() {
@@ -335,7 +577,11 @@ mod tests {
         );
         assert_eq!(
             Board::parse_fen_side_to_move("g"),
-            Err(ParseFenError::UnexpectedChar)
+            Err(Board::unexpected_char(FenField::SideToMove, 'g', 0))
+        );
+        assert_eq!(
+            Board::parse_fen_side_to_move("white"),
+            Err(Board::unexpected_char(FenField::SideToMove, 'h', 1))
         );
     }
 
@@ -353,6 +599,10 @@ mod tests {
             Board::parse_fen_castling_rights("Kkq"),
             Ok([CastleRights::KingSide, CastleRights::Both])
         );
+        assert_eq!(
+            Board::parse_fen_castling_rights("K-"),
+            Err(Board::unexpected_char(FenField::CastlingRights, '-', 1))
+        );
     }
 
     #[test]
@@ -361,7 +611,147 @@ mod tests {
         assert_eq!(Board::parse_fen_en_passant_square("a6"), Ok(Some(40)));
         assert_eq!(
             Board::parse_fen_en_passant_square("g5"),
-            Err(ParseFenError::UnexpectedChar)
+            Err(Board::unexpected_char(FenField::EnPassantSquare, '5', 1))
+        );
+        assert_eq!(
+            Board::parse_fen_en_passant_square("e3e"),
+            Err(Board::unexpected_char(FenField::EnPassantSquare, 'e', 2))
+        );
+        assert!(Board::parse_fen_en_passant_square("e").is_err());
+    }
+
+    #[test]
+    fn parse_fen_bad_placement_tests() {
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/8/8/8/8/8/4K3/8"),
+            Err(ParseFenError::TooManyRanks)
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/8/8/8/8/4K3"),
+            Err(ParseFenError::TooFewRanks)
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/8/8/8/8/8/4K3p"),
+            Err(ParseFenError::RankTooLong(1))
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k4/8/8/8/8/8/8/4K3"),
+            Err(ParseFenError::RankTooLong(8))
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/7/8/8/8/8/4K3"),
+            Err(ParseFenError::RankTooShort(6))
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/8/8/8/8/8/4K2"),
+            Err(ParseFenError::RankTooShort(1))
+        );
+        assert_eq!(
+            Board::parse_fen_positions("4k3/8/8/3x4/8/8/8/4K3"),
+            Err(Board::unexpected_char(FenField::Placement, 'x', 9))
+        );
+    }
+
+    #[test]
+    fn validate_fen_state_tests() {
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(ParseFenError::MissingKing(Side::Black))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"),
+            Err(ParseFenError::TooManyKings(Side::White))
+        );
+        assert_eq!(
+            Board::from_fen("3pk3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(ParseFenError::PawnOnBackRank(D8))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 w K - 0 1"),
+            Err(ParseFenError::InvalidCastlingRights(Side::White))
+        );
+        assert_eq!(
+            Board::from_fen("r3k3/8/8/8/8/8/8/4K3 w k - 0 1"),
+            Err(ParseFenError::InvalidCastlingRights(Side::Black))
+        );
+
+        // the en passant square must match the side to move and a pawn that just moved two squares
+        assert!(Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").is_ok());
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"),
+            Err(ParseFenError::InvalidEnPassantSquare(E3))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - e3 0 1"),
+            Err(ParseFenError::InvalidEnPassantSquare(E3))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/4P3/8/4P3/4K3 b - e3 0 1"),
+            Err(ParseFenError::InvalidEnPassantSquare(E3))
+        );
+
+        // the side to move could take the king
+        assert_eq!(
+            Board::from_fen("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(ParseFenError::OpponentInCheck(Side::Black))
+        );
+        assert!(Board::from_fen("4k3/4R3/8/8/8/8/8/4K3 b - - 0 1").is_ok());
+    }
+
+    #[test]
+    fn parse_fen_move_counters_tests() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 37 52").unwrap();
+        assert_eq!(board.state.halfmove_clock, 37);
+        assert_eq!(board.state.fullmove_number, 52);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+        assert_eq!(board.state.halfmove_clock, 0);
+        assert_eq!(board.state.fullmove_number, 1);
+
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - -1 2"),
+            Err(ParseFenError::InvalidMoveCounter(FenField::HalfmoveClock))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 x"),
+            Err(ParseFenError::InvalidMoveCounter(FenField::FullmoveNumber))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 0"),
+            Err(ParseFenError::InvalidMoveCounter(FenField::FullmoveNumber))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1 1"),
+            Err(ParseFenError::BadFenFormat("wrong number of args"))
+        );
+    }
+
+    #[test]
+    fn to_fen_tests() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 12 10",
+        ];
+        for fen in fens.iter() {
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(), *fen);
+        }
+        assert_eq!(Board::default().to_fen(), fens[0]);
+
+        // counters are kept up to date while moves are played
+        let board = parse_uci_position("position startpos moves e2e4 e7e5 g1f3 b8c6 f1b5").unwrap();
+        assert_eq!(
+            board.to_fen(),
+            "r1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3"
+        );
+        let board = parse_uci_position("position startpos moves e2e4 d7d5 e4d5").unwrap();
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2"
         );
     }
 
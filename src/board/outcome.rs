@@ -0,0 +1,105 @@
+use crate::{
+    bitboard::Bitboard,
+    defs::{Piece, Side},
+};
+
+use super::{Board, DrawReason};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Outcome {
+    Ongoing,
+    // holds the winning side
+    Checkmate(Side),
+    Stalemate,
+    Draw(DrawReason),
+}
+
+impl Board {
+    pub fn is_check(&self) -> bool {
+        let side = self.state.side_to_move;
+        match self.state.bitboards[side as usize][Piece::King as usize].get_ls1b_index() {
+            Some(king_square) => self.is_square_attacked(king_square, side.get_opposite_side()),
+            None => false,
+        }
+    }
+
+    // pieces giving check to the side to move
+    pub fn get_checkers(&self) -> Bitboard {
+        let side = self.state.side_to_move;
+        match self.state.bitboards[side as usize][Piece::King as usize].get_ls1b_index() {
+            Some(king_square) => self.get_attackers(
+                king_square,
+                side.get_opposite_side(),
+                self.state.occupancies[2],
+            ),
+            None => Bitboard(0),
+        }
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        !self.generate_legal_moves().is_empty()
+    }
+
+    pub fn is_checkmate(&self) -> bool {
+        self.is_check() && !self.has_legal_move()
+    }
+
+    pub fn is_stalemate(&self) -> bool {
+        !self.is_check() && !self.has_legal_move()
+    }
+
+    // a checkmate stands even when the move delivering it also completes a draw by move rule
+    pub fn outcome(&self) -> Outcome {
+        if !self.has_legal_move() {
+            return match self.is_check() {
+                true => Outcome::Checkmate(self.state.side_to_move.get_opposite_side()),
+                false => Outcome::Stalemate,
+            };
+        }
+        match self.draw_reason() {
+            Some(reason) => Outcome::Draw(reason),
+            None => Outcome::Ongoing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_tests() {
+        assert!(!Board::default().is_check());
+        assert!(Board::from_fen("4k3/8/8/8/8/8/8/4RK2 b - - 0 1")
+            .unwrap()
+            .is_check());
+        assert!(!Board::from_fen("4k3/8/8/8/8/8/8/3R1K2 w - - 0 1")
+            .unwrap()
+            .is_check());
+    }
+
+    #[test]
+    fn outcome_tests() {
+        let board = Board::default();
+        assert_eq!(board.outcome(), Outcome::Ongoing);
+
+        let board = Board::from_fen("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(board.is_checkmate());
+        assert!(!board.is_stalemate());
+        assert_eq!(board.outcome(), Outcome::Checkmate(Side::White));
+
+        let board = Board::from_fen("k7/8/1Q6/8/8/8/8/7K b - - 0 1").unwrap();
+        assert!(board.is_stalemate());
+        assert!(!board.is_checkmate());
+        assert_eq!(board.outcome(), Outcome::Stalemate);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            board.outcome(),
+            Outcome::Draw(DrawReason::InsufficientMaterial)
+        );
+
+        let board = Board::from_fen("k7/1Q6/1K6/8/8/8/8/8 b - - 100 80").unwrap();
+        assert_eq!(board.outcome(), Outcome::Checkmate(Side::White));
+    }
+}
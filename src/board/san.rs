@@ -0,0 +1,274 @@
+use std::convert::TryFrom;
+
+use crate::{
+    constants::{ASCII_PIECE, SQUARE_NAME},
+    defs::{Piece, Promotion, Side, Square},
+    move_generator::movelist::Move,
+};
+
+use super::Board;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseSanError {
+    EmptyString,
+    BadSanFormat,
+    IllegalMove,
+    // more than one legal move matches
+    AmbiguousMove,
+}
+
+impl Board {
+    // the move must be legal in the current position
+    pub fn move_to_san(&self, mv: Move) -> String {
+        let mut san = if mv.get_castling() {
+            match mv.get_target_square() % 8 {
+                6 => String::from("O-O"),
+                _ => String::from("O-O-O"),
+            }
+        } else {
+            self.get_san_body(mv)
+        };
+
+        let mut board = self.clone();
+        if board.make_move(mv, false).is_ok() {
+            if board.is_checkmate() {
+                san.push('#');
+            } else if board.is_check() {
+                san.push('+');
+            }
+        }
+        san
+    }
+
+    // piece, disambiguation, capture, target square and promotion
+    fn get_san_body(&self, mv: Move) -> String {
+        let source = mv.get_source_square();
+        let target = mv.get_target_square();
+        let mut san = String::new();
+
+        if mv.get_piece() == Piece::Pawn {
+            if mv.get_capture() {
+                san.push(get_file_char(source));
+            }
+        } else {
+            san.push_str(ASCII_PIECE[Side::White as usize][mv.get_piece() as usize]);
+
+            let others: Vec<Move> = self
+                .generate_legal_moves()
+                .into_iter()
+                .filter(|other| {
+                    other.get_piece() == mv.get_piece()
+                        && other.get_target_square() == target
+                        && other.get_source_square() != source
+                })
+                .collect();
+            if !others.is_empty() {
+                let same_file = others
+                    .iter()
+                    .any(|other| other.get_source_square() % 8 == source % 8);
+                let same_rank = others
+                    .iter()
+                    .any(|other| other.get_source_square() / 8 == source / 8);
+                // the file is preferred, then the rank, then the whole square
+                if !same_file {
+                    san.push(get_file_char(source));
+                } else if !same_rank {
+                    san.push(get_rank_char(source));
+                } else {
+                    san.push_str(SQUARE_NAME[source as usize]);
+                }
+            }
+        }
+
+        if mv.get_capture() {
+            san.push('x');
+        }
+        san.push_str(SQUARE_NAME[target as usize]);
+        if let Some(promotion) = mv.get_promotion() {
+            san.push('=');
+            san.push_str(ASCII_PIECE[Side::White as usize][Piece::from(promotion) as usize]);
+        }
+        san
+    }
+
+    // annotations and a missing capture sign or promotion `=` are tolerated
+    pub fn parse_san(&self, san: &str) -> Result<Move, ParseSanError> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        if san.is_empty() {
+            return Err(ParseSanError::EmptyString);
+        }
+
+        let moves = self.generate_legal_moves();
+
+        if let Some(long) = match san {
+            "O-O" | "0-0" => Some(false),
+            "O-O-O" | "0-0-0" => Some(true),
+            _ => None,
+        } {
+            return moves
+                .into_iter()
+                .find(|mv| mv.get_castling() && (mv.get_target_square() % 8 == 2) == long)
+                .ok_or(ParseSanError::IllegalMove);
+        }
+
+        let mut chars: Vec<char> = san.chars().collect();
+
+        let piece = match chars[0] {
+            'K' => Piece::King,
+            'Q' => Piece::Queen,
+            'R' => Piece::Rook,
+            'B' => Piece::Bishop,
+            'N' => Piece::Knight,
+            _ => Piece::Pawn,
+        };
+        if piece != Piece::Pawn {
+            chars.remove(0);
+        }
+
+        let promotion = match chars.last().map(|c| c.to_ascii_lowercase()) {
+            Some(c @ ('q' | 'r' | 'b' | 'n')) if piece == Piece::Pawn => {
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(Promotion::try_from(c).map_err(|_| ParseSanError::BadSanFormat)?)
+            }
+            _ => None,
+        };
+
+        if chars.len() < 2 {
+            return Err(ParseSanError::BadSanFormat);
+        }
+        let rank = chars
+            .pop()
+            .and_then(parse_rank)
+            .ok_or(ParseSanError::BadSanFormat)?;
+        let file = chars
+            .pop()
+            .and_then(parse_file)
+            .ok_or(ParseSanError::BadSanFormat)?;
+        let target = file + rank * 8;
+
+        let mut source_file = None;
+        let mut source_rank = None;
+        for c in chars {
+            match (c, parse_file(c), parse_rank(c)) {
+                ('x', _, _) => {}
+                (_, Some(file), _) => source_file = Some(file),
+                (_, _, Some(rank)) => source_rank = Some(rank),
+                _ => return Err(ParseSanError::BadSanFormat),
+            }
+        }
+
+        let mut candidates = moves.into_iter().filter(|mv| {
+            mv.get_piece() == piece
+                && mv.get_target_square() == target
+                && mv.get_promotion() == promotion
+                && !mv.get_castling()
+                && source_file.is_none_or(|file| mv.get_source_square() % 8 == file)
+                && source_rank.is_none_or(|rank| mv.get_source_square() / 8 == rank)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(mv), None) => Ok(mv),
+            (Some(_), Some(_)) => Err(ParseSanError::AmbiguousMove),
+            (None, _) => Err(ParseSanError::IllegalMove),
+        }
+    }
+}
+
+fn get_file_char(square: Square) -> char {
+    (b'a' + (square % 8) as u8) as char
+}
+
+fn get_rank_char(square: Square) -> char {
+    (b'1' + (square / 8) as u8) as char
+}
+
+fn parse_file(c: char) -> Option<Square> {
+    match c {
+        'a'..='h' => Some(c as Square - 'a' as Square),
+        _ => None,
+    }
+}
+
+fn parse_rank(c: char) -> Option<Square> {
+    match c {
+        '1'..='8' => Some(c as Square - '1' as Square),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    #[test]
+    fn move_to_san_tests() {
+        let board = Board::default();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(G1, F3, Piece::Knight, None, false, false, false, false)), "Nf3");
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(E2, E4, Piece::Pawn, None, false, true, false, false)), "e4");
+
+        // both rooks and both knights reach the same squares
+        let board = Board::from_fen("3k4/8/8/R6R/8/1N6/8/1N2K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(A5, D5, Piece::Rook, None, false, false, false, false)), "Rad5+");
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(B1, D2, Piece::Knight, None, false, false, false, false)), "N1d2");
+
+        let board = Board::from_fen("6k1/8/8/8/8/Q1Q5/8/Q3K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(A3, B2, Piece::Queen, None, false, false, false, false)), "Qa3b2");
+
+        let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(E5, D6, Piece::Pawn, None, true, false, true, false)), "exd6");
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(E1, G1, Piece::King, None, false, false, false, true)), "O-O");
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(E1, C1, Piece::King, None, false, false, false, true)), "O-O-O");
+
+        let board = Board::from_fen("3k4/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(B7, B8, Piece::Pawn, Some(Promotion::Queen), false, false, false, false)), "b8=Q+");
+
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.move_to_san(Move::new(A1, A8, Piece::Rook, None, false, false, false, false)), "Ra8#");
+    }
+
+    #[test]
+    fn parse_san_tests() {
+        let board = Board::from_fen("3k4/8/8/R6R/8/1N6/8/1N2K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("Rad5+"), Ok(Move::new(A5, D5, Piece::Rook, None, false, false, false, false)));
+        assert_eq!(board.parse_san("Rd5"), Err(ParseSanError::AmbiguousMove));
+        assert_eq!(board.parse_san("Nd2"), Err(ParseSanError::AmbiguousMove));
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("N3d2"), Ok(Move::new(B3, D2, Piece::Knight, None, false, false, false, false)));
+        assert_eq!(board.parse_san("Qd5"), Err(ParseSanError::IllegalMove));
+        assert_eq!(board.parse_san("Rz5"), Err(ParseSanError::BadSanFormat));
+        assert_eq!(board.parse_san("+"), Err(ParseSanError::EmptyString));
+
+        let board = Board::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("exd6"), Ok(Move::new(E5, D6, Piece::Pawn, None, true, false, true, false)));
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("O-O-O"), Ok(Move::new(E1, C1, Piece::King, None, false, false, false, true)));
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("bxa8=N"), Ok(Move::new(B7, A8, Piece::Pawn, Some(Promotion::Knight), true, false, false, false)));
+        #[rustfmt::skip]
+        assert_eq!(board.parse_san("b8Q!?"), Ok(Move::new(B7, B8, Piece::Pawn, Some(Promotion::Queen), false, false, false, false)));
+        assert_eq!(board.parse_san("b8"), Err(ParseSanError::IllegalMove));
+
+        // every legal move survives a round trip
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        for mv in board.generate_legal_moves().into_iter() {
+            assert_eq!(board.parse_san(&board.move_to_san(mv)), Ok(mv));
+        }
+    }
+}
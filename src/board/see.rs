@@ -0,0 +1,135 @@
+use crate::{
+    bitboard::Bitboard,
+    defs::{Piece, Side},
+    move_generator::movelist::Move,
+};
+
+use super::Board;
+
+// piece values used to resolve exchanges, indexed by Piece
+pub const SEE_VALUES: [i32; 6] = [20_000, 900, 100, 300, 300, 500];
+
+// cheapest pieces are sent in first
+const CAPTURE_ORDER: [Piece; 6] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King,
+];
+
+impl Board {
+    // static exchange evaluation : material won by the side to move once every piece attacking
+    // the target square has recaptured, each side being free to stop when it is losing
+    pub fn see(&self, mv: Move) -> i32 {
+        let target = mv.get_target_square();
+        let mut gain = [0; 32];
+        let mut depth = 0;
+        let mut side = self.state.side_to_move;
+        let mut occupancy = self.state.occupancies[2];
+
+        gain[0] = match self.get_piece_on(target) {
+            Some((_, piece)) => SEE_VALUES[piece as usize],
+            None if mv.get_en_passant() => SEE_VALUES[Piece::Pawn as usize],
+            None => 0,
+        };
+        let mut piece_value = match mv.get_promotion() {
+            Some(promotion) => {
+                let promoted = SEE_VALUES[Piece::from(promotion) as usize];
+                gain[0] += promoted - SEE_VALUES[Piece::Pawn as usize];
+                promoted
+            }
+            None => SEE_VALUES[mv.get_piece() as usize],
+        };
+
+        occupancy &= !Bitboard::from_square(mv.get_source_square());
+        if mv.get_en_passant() {
+            let captured = match side {
+                Side::White => target - 8,
+                Side::Black => target + 8,
+            };
+            occupancy &= !Bitboard::from_square(captured);
+        }
+
+        loop {
+            depth += 1;
+            side = side.get_opposite_side();
+            // score if the piece standing on the target square gets captured
+            gain[depth] = piece_value - gain[depth - 1];
+            if depth == gain.len() - 1 {
+                break;
+            }
+
+            // sliders hidden behind the pieces already traded join in as the occupancy shrinks
+            let attackers = (self.get_attackers(target, Side::White, occupancy)
+                | self.get_attackers(target, Side::Black, occupancy))
+                & occupancy;
+            let side_attackers = attackers & self.state.occupancies[side as usize];
+            let attacker = CAPTURE_ORDER.iter().find_map(|piece| {
+                (self.state.bitboards[side as usize][*piece as usize] & side_attackers)
+                    .get_ls1b_index()
+                    .map(|square| (*piece, square))
+            });
+
+            match attacker {
+                // the king can not capture a defended piece
+                Some((Piece::King, _))
+                    if attackers & self.state.occupancies[side.get_opposite_side() as usize]
+                        != 0 =>
+                {
+                    break
+                }
+                Some((piece, square)) => {
+                    occupancy &= !Bitboard::from_square(square);
+                    piece_value = SEE_VALUES[piece as usize];
+                }
+                None => break,
+            }
+        }
+
+        // the last entry assumes a recapture that did not happen
+        while depth > 1 {
+            depth -= 1;
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        }
+        gain[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::*, defs::Promotion};
+
+    #[test]
+    fn see_tests() {
+        #[rustfmt::skip]
+        let rook_takes_pawn = Move::new(D1, D5, Piece::Rook, None, true, false, false, false);
+
+        let board = Board::from_fen("4k3/8/8/3p4/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.see(rook_takes_pawn), 100);
+
+        // the pawn is defended, the rook is lost for a pawn
+        let board = Board::from_fen("4k3/8/4p3/3p4/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.see(rook_takes_pawn), -400);
+
+        // the second rook recaptures through the first one
+        #[rustfmt::skip]
+        let front_rook_takes_pawn = Move::new(D2, D5, Piece::Rook, None, true, false, false, false);
+        let board = Board::from_fen("4k3/8/4p3/3p4/8/8/3R4/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.see(front_rook_takes_pawn), -300);
+
+        // the defending king can not recapture a piece which is still protected
+        let board = Board::from_fen("8/8/3k4/3p4/8/8/3R4/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.see(front_rook_takes_pawn), 100);
+
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.see(Move::new(E5, D6, Piece::Pawn, None, true, false, true, false)), 100);
+
+        let board = Board::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(board.see(Move::new(B7, B8, Piece::Pawn, Some(Promotion::Queen), false, false, false, false)), 800);
+    }
+}
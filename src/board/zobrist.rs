@@ -0,0 +1,176 @@
+use crate::defs::{Side, Square};
+
+use super::{Board, BoardState};
+
+pub struct ZobristKeys {
+    pieces: [[[u64; 64]; 6]; 2],
+    castling: [[u64; 4]; 2],
+    en_passant: [u64; 8],
+    side: u64,
+}
+
+// keys are generated at compile time so every board shares the exact same values
+pub static ZOBRIST_KEYS: ZobristKeys = ZobristKeys::new();
+
+// xorshift64* generator, only used to fill the key tables
+const fn next_random(state: u64) -> (u64, u64) {
+    let mut x = state;
+    x ^= x >> 12;
+    x ^= x << 25;
+    x ^= x >> 27;
+    (x, x.wrapping_mul(0x2545_f491_4f6c_dd1d))
+}
+
+impl ZobristKeys {
+    const fn new() -> Self {
+        let mut keys = Self {
+            pieces: [[[0; 64]; 6]; 2],
+            castling: [[0; 4]; 2],
+            en_passant: [0; 8],
+            side: 0,
+        };
+        let mut seed = 0x4b6e_6967_6874_696d;
+        let mut random;
+
+        let mut side = 0;
+        while side < 2 {
+            let mut piece = 0;
+            while piece < 6 {
+                let mut square = 0;
+                while square < 64 {
+                    (seed, random) = next_random(seed);
+                    keys.pieces[side][piece][square] = random;
+                    square += 1;
+                }
+                piece += 1;
+            }
+            let mut rights = 0;
+            while rights < 4 {
+                (seed, random) = next_random(seed);
+                keys.castling[side][rights] = random;
+                rights += 1;
+            }
+            side += 1;
+        }
+
+        let mut file = 0;
+        while file < 8 {
+            (seed, random) = next_random(seed);
+            keys.en_passant[file] = random;
+            file += 1;
+        }
+
+        (_, random) = next_random(seed);
+        keys.side = random;
+
+        keys
+    }
+
+    pub fn piece(&self, side: Side, piece: usize, square: Square) -> u64 {
+        self.pieces[side as usize][piece][square as usize]
+    }
+
+    pub fn castling(&self, state: &BoardState) -> u64 {
+        self.castling[0][state.castling_rights[0].to_index()]
+            ^ self.castling[1][state.castling_rights[1].to_index()]
+    }
+
+    pub fn en_passant(&self, en_passant_square: Option<Square>) -> u64 {
+        match en_passant_square {
+            Some(square) => self.en_passant[(square % 8) as usize],
+            None => 0,
+        }
+    }
+
+    pub fn side(&self) -> u64 {
+        self.side
+    }
+}
+
+impl Board {
+    pub fn get_hash(&self) -> u64 {
+        self.state.hash
+    }
+
+    // full recomputation of the position key, make_move keeps it up to date incrementally
+    pub fn compute_hash(state: &BoardState) -> u64 {
+        let mut hash = 0;
+
+        for side in [Side::White, Side::Black] {
+            for (piece, bitboard) in state.bitboards[side as usize].iter().enumerate() {
+                for square in bitboard.into_iter() {
+                    hash ^= ZOBRIST_KEYS.piece(side, piece, square);
+                }
+            }
+        }
+
+        hash ^= ZOBRIST_KEYS.castling(state);
+        hash ^= ZOBRIST_KEYS.en_passant(state.en_passant_square);
+
+        if state.side_to_move == Side::Black {
+            hash ^= ZOBRIST_KEYS.side();
+        }
+
+        hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        communication::uci::parse_uci_position,
+        constants::*,
+        defs::{Piece, Promotion},
+        move_generator::movelist::Move,
+    };
+
+    #[test]
+    fn compute_hash_tests() {
+        let board = Board::default();
+        assert_eq!(board.get_hash(), Board::compute_hash(board.get_state()));
+        assert_ne!(board.get_hash(), 0);
+
+        let white = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let black = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert_eq!(white.get_hash() ^ black.get_hash(), ZOBRIST_KEYS.side());
+
+        let castle = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+        let no_castle = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert_ne!(castle.get_hash(), no_castle.get_hash());
+    }
+
+    #[test]
+    fn incremental_hash_tests() {
+        let board = parse_uci_position("position startpos moves e2e4 e7e5 g1f3").unwrap();
+        let fen_board =
+            Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2")
+                .unwrap();
+        assert_eq!(board.get_hash(), fen_board.get_hash());
+
+        // knights going back and forth transpose to the initial position
+        let board = parse_uci_position("position startpos moves g1f3 g8f6 f3g1 f6g8").unwrap();
+        assert_eq!(board.get_hash(), Board::default().get_hash());
+
+        // castling, en passant and promotion all go through the incremental update
+        let mut board =
+            Board::from_fen("r3k2r/1pp2ppp/8/3Pp3/8/8/p4PPP/RN2K2R w KQkq e6 0 1").unwrap();
+        let initial_hash = board.get_hash();
+        #[rustfmt::skip]
+        let moves = [
+            Move::new(D5, E6, Piece::Pawn, None, true, false, true, false),
+            Move::new(E8, C8, Piece::King, None, false, false, false, true),
+            Move::new(E1, G1, Piece::King, None, false, false, false, true),
+            Move::new(A2, B1, Piece::Pawn, Some(Promotion::Queen), true, false, false, false),
+        ];
+        for mv in moves.iter() {
+            board.make_move(*mv, false).unwrap();
+            assert_eq!(board.get_hash(), Board::compute_hash(board.get_state()));
+        }
+
+        for _ in moves.iter() {
+            board.take_back_move();
+        }
+        assert_eq!(board.get_hash(), initial_hash);
+    }
+}
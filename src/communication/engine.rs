@@ -0,0 +1,351 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use crate::{
+    board::Board,
+    search::{thread::SearchThread, tt::TranspositionTable, Search, SearchResult},
+};
+
+use super::{
+    options::{
+        Options, CLEAR_HASH, HASH, MIN_ELO, MOVE_OVERHEAD, MULTI_PV, THREADS, UCI_ELO,
+        UCI_LIMIT_STRENGTH,
+    },
+    uci::{parse_uci_go, parse_uci_position, parse_uci_setoption, UciError},
+};
+
+pub const ENGINE_NAME: &str = "Knightime";
+pub const ENGINE_AUTHOR: &str = "niconico";
+
+// with UCI_LimitStrength, every ply the search may go deeper costs that much elo
+const ELO_PER_PLY: i64 = 200;
+
+#[derive(Default)]
+pub struct Engine {
+    board: Board,
+    tt: Arc<TranspositionTable>,
+    search: SearchThread,
+    options: Options,
+}
+
+impl Engine {
+    pub fn run<R, W>(&mut self, input: R, output: W) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write + Send + 'static,
+    {
+        let output = Arc::new(Mutex::new(output));
+        for line in input.lines() {
+            if !self.handle_command(&line?, &output)? {
+                break;
+            }
+        }
+        // the gui may close the input while the engine is still thinking
+        self.search.stop();
+        Ok(())
+    }
+
+    // returns false once the engine has been asked to quit, the search thread shares `output`
+    pub fn handle_command<W>(&mut self, command: &str, output: &Arc<Mutex<W>>) -> io::Result<bool>
+    where
+        W: Write + Send + 'static,
+    {
+        let command = command.trim();
+
+        match command.split_whitespace().next() {
+            Some("uci") => {
+                send_line(
+                    output,
+                    &format!("id name {} {}", ENGINE_NAME, env!("CARGO_PKG_VERSION")),
+                )?;
+                send_line(output, &format!("id author {}", ENGINE_AUTHOR))?;
+                for option in self.options.to_uci_strings() {
+                    send_line(output, &option)?;
+                }
+                send_line(output, "uciok")?;
+            }
+            Some("isready") => send_line(output, "readyok")?,
+            Some("setoption") => {
+                if let Err(err) = self.set_option(command) {
+                    send_line(output, &format!("info string invalid option: {:?}", err))?;
+                }
+            }
+            Some("ucinewgame") => {
+                self.search.stop();
+                self.board = Board::default();
+                self.tt.clear();
+            }
+            Some("position") => match parse_uci_position(command) {
+                Ok(board) => self.board = board,
+                Err(err) => send_line(output, &format!("info string invalid position: {:?}", err))?,
+            },
+            Some("go") => match parse_uci_go(command, &self.board) {
+                Ok(mut limits) => {
+                    if self.options.get_check(UCI_LIMIT_STRENGTH) {
+                        let depth = get_strength_depth(self.options.get_spin(UCI_ELO));
+                        limits.depth = Some(limits.depth.map_or(depth, |d| d.min(depth)));
+                    }
+                    let info_output = Arc::clone(output);
+                    let move_overhead = self.options.get_spin(MOVE_OVERHEAD) as u64;
+                    let search = Search::new(self.board.clone(), Arc::clone(&self.tt))
+                        .with_move_overhead(Duration::from_millis(move_overhead))
+                        .with_multipv(self.options.get_spin(MULTI_PV) as usize)
+                        .with_threads(self.options.get_spin(THREADS) as usize)
+                        .with_info_callback(Box::new(move |info| {
+                            let _ = send_line(&info_output, &info.to_uci_string());
+                        }));
+                    let output = Arc::clone(output);
+                    self.search.start(search, limits, move |result| {
+                        // nobody is left to tell if the output is gone
+                        let _ = send_line(&output, &get_bestmove_line(&result));
+                    });
+                }
+                Err(err) => send_line(
+                    output,
+                    &format!("info string invalid go command: {:?}", err),
+                )?,
+            },
+            Some("stop") => self.search.stop(),
+            Some("ponderhit") => self.search.ponderhit(),
+            Some("quit") => {
+                self.search.stop();
+                return Ok(false);
+            }
+            Some(_) => send_line(output, &format!("info string unknown command: {}", command))?,
+            None => {}
+        }
+
+        Ok(true)
+    }
+
+    fn set_option(&mut self, command: &str) -> Result<(), UciError> {
+        let (name, value) = parse_uci_setoption(command)?;
+
+        // the other options are read when they are needed
+        match self.options.set(&name, value.as_deref())? {
+            HASH => {
+                self.search.stop();
+                let size = self.options.get_spin(HASH) as usize;
+                self.tt = Arc::new(TranspositionTable::new(size));
+            }
+            CLEAR_HASH => {
+                self.search.stop();
+                self.tt.clear();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// the weakest setting searches a single ply
+fn get_strength_depth(elo: i64) -> u32 {
+    ((elo - MIN_ELO) / ELO_PER_PLY + 1) as u32
+}
+
+fn send_line<W: Write>(output: &Mutex<W>, line: &str) -> io::Result<()> {
+    let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+    writeln!(output, "{}", line)?;
+    output.flush()
+}
+
+// the gui always expects a bestmove, `0000` when there is no legal move
+fn get_bestmove_line(result: &SearchResult) -> String {
+    match (result.best_move, result.pv.get(1)) {
+        (Some(mv), Some(ponder)) => format!(
+            "bestmove {} ponder {}",
+            mv.to_uci_string(),
+            ponder.to_uci_string()
+        ),
+        (Some(mv), None) => format!("bestmove {}", mv.to_uci_string()),
+        (None, _) => String::from("bestmove 0000"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tt::DEFAULT_HASH_SIZE;
+    use std::thread;
+
+    type Output = Arc<Mutex<Vec<u8>>>;
+
+    // waits for the search so that its result is part of the output
+    fn send(engine: &mut Engine, command: &str) -> (bool, String) {
+        let output = Output::default();
+        let running = engine.handle_command(command, &output).unwrap();
+        engine.search.wait();
+        (running, read(&output))
+    }
+
+    fn read(output: &Output) -> String {
+        String::from_utf8(output.lock().unwrap().split_off(0)).unwrap()
+    }
+
+    // lets the test read what was written once the engine owns the writer
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Output);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn handshake_tests() {
+        let mut engine = Engine::default();
+
+        let (running, output) = send(&mut engine, "uci");
+        assert!(running);
+        assert!(output.starts_with("id name Knightime"));
+        assert!(output.contains("id author niconico\n"));
+        assert!(output.ends_with("uciok\n"));
+
+        let (_, output) = send(&mut engine, "isready");
+        assert_eq!(output, "readyok\n");
+
+        let (running, output) = send(&mut engine, "quit");
+        assert!(!running);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn position_and_go_tests() {
+        let mut engine = Engine::default();
+
+        let (_, output) = send(&mut engine, "position startpos moves e2e4");
+        assert_eq!(output, "");
+        assert_ne!(engine.board, Board::default());
+
+        let (_, output) = send(&mut engine, "go depth 1");
+        assert!(output.starts_with("info depth 1 "));
+        assert!(output.lines().last().unwrap().starts_with("bestmove "));
+
+        let (_, output) = send(&mut engine, "go movetime 50");
+        assert!(output.lines().last().unwrap().starts_with("bestmove "));
+
+        let (_, output) = send(&mut engine, "go wtime x");
+        assert!(output.starts_with("info string invalid go command"));
+
+        let (_, output) = send(&mut engine, "position startpos moves e2e5");
+        assert!(output.starts_with("info string"));
+
+        send(&mut engine, "ucinewgame");
+        assert_eq!(engine.board, Board::default());
+        assert_eq!(engine.tt.hashfull(), 0);
+    }
+
+    #[test]
+    fn setoption_tests() {
+        let mut engine = Engine::default();
+        assert_eq!(engine.tt.get_size(), DEFAULT_HASH_SIZE);
+
+        let (_, output) = send(&mut engine, "uci");
+        assert!(output.contains("option name Threads type spin default 1 min 1 max 256\n"));
+        assert!(output.contains("option name UCI_Chess960 type check default false\n"));
+        assert!(output.contains("option name UCI_LimitStrength type check default false\n"));
+        let (_, output) = send(&mut engine, "setoption name UCI_Chess960 value true");
+        assert!(output.starts_with("info string invalid option: UnsupportedOptionValue"));
+        let (_, output) = send(&mut engine, "setoption name UCI_Chess960 value false");
+        assert_eq!(output, "");
+
+        let (_, output) = send(&mut engine, "setoption name Hash value 2");
+        assert_eq!(output, "");
+        assert_eq!(engine.tt.get_size(), 2);
+
+        let (_, output) = send(&mut engine, "setoption name Hash value 0");
+        assert!(output.starts_with("info string invalid option"));
+        assert_eq!(engine.tt.get_size(), 2);
+
+        send(&mut engine, "go depth 3");
+        assert_ne!(engine.tt.hashfull(), 0);
+        let (_, output) = send(&mut engine, "setoption name Clear Hash");
+        assert_eq!(output, "");
+        assert_eq!(engine.tt.hashfull(), 0);
+
+        send(&mut engine, "setoption name Threads value 2");
+        send(&mut engine, "setoption name MultiPV value 2");
+        let (_, output) = send(&mut engine, "go depth 2");
+        assert!(output.contains("info depth 2 seldepth"));
+        assert!(output.contains(" multipv 2 "));
+
+        let (_, output) = send(&mut engine, "setoption name move overhead value 100");
+        assert_eq!(output, "");
+        assert_eq!(engine.options.get_spin(MOVE_OVERHEAD), 100);
+
+        send(&mut engine, "setoption name MultiPV value 1");
+        send(&mut engine, "setoption name UCI_LimitStrength value true");
+        send(&mut engine, "setoption name UCI_Elo value 1200");
+        let (_, output) = send(&mut engine, "go depth 5");
+        assert!(output.contains("info depth 2 "));
+        assert!(!output.contains("info depth 3 "));
+        let (_, output) = send(&mut engine, "setoption name UCI_Elo value 3000");
+        assert!(output.contains("OptionOutOfRange"));
+
+        let (_, output) = send(&mut engine, "setoption name Unknown value 0");
+        assert!(output.contains("UnknownOption"));
+    }
+
+    #[test]
+    fn run_tests() {
+        let mut engine = Engine::default();
+        let output = SharedBuffer::default();
+        let input = "uci\nisready\nposition startpos\ngo depth 1\nquit\nisready\n";
+        engine.run(input.as_bytes(), output.clone()).unwrap();
+
+        let output = read(&output.0);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[lines.len() - 4], "uciok");
+        assert_eq!(lines[lines.len() - 3], "readyok");
+        assert!(lines[lines.len() - 2].starts_with("info depth 1 "));
+        assert!(lines[lines.len() - 1].starts_with("bestmove "));
+    }
+
+    #[test]
+    fn stop_and_ponderhit_tests() {
+        let mut engine = Engine::default();
+        let output = Output::default();
+
+        // an infinite search only answers once it is stopped
+        engine.handle_command("go infinite", &output).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        assert!(engine.search.is_running());
+        assert!(!read(&output).contains("bestmove"));
+        engine.handle_command("stop", &output).unwrap();
+        assert!(read(&output)
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with("bestmove "));
+
+        engine
+            .handle_command("go ponder wtime 1000 btime 1000", &output)
+            .unwrap();
+        thread::sleep(Duration::from_millis(20));
+        assert!(!read(&output).contains("bestmove"));
+        engine.handle_command("ponderhit", &output).unwrap();
+        engine.search.wait();
+        assert!(read(&output)
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with("bestmove "));
+
+        // quitting while thinking still sends the move
+        engine.handle_command("go infinite", &output).unwrap();
+        assert!(!engine.handle_command("quit", &output).unwrap());
+        assert!(read(&output)
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with("bestmove "));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::search::{
+    time::DEFAULT_MOVE_OVERHEAD,
+    tt::{DEFAULT_HASH_SIZE, MAX_HASH_SIZE, MIN_HASH_SIZE},
+};
+
+use super::uci::UciError;
+
+pub const HASH: &str = "Hash";
+pub const CLEAR_HASH: &str = "Clear Hash";
+pub const THREADS: &str = "Threads";
+pub const MULTI_PV: &str = "MultiPV";
+pub const MOVE_OVERHEAD: &str = "Move Overhead";
+pub const PONDER: &str = "Ponder";
+pub const UCI_CHESS960: &str = "UCI_Chess960";
+pub const UCI_LIMIT_STRENGTH: &str = "UCI_LimitStrength";
+pub const UCI_ELO: &str = "UCI_Elo";
+
+pub const MAX_THREADS: i64 = 256;
+pub const MAX_MULTI_PV: i64 = 256;
+pub const MAX_MOVE_OVERHEAD: i64 = 5000;
+pub const MIN_ELO: i64 = 1000;
+pub const MAX_ELO: i64 = 2600;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionType {
+    Check { default: bool },
+    Spin { default: i64, min: i64, max: i64 },
+    String { default: String },
+    Button,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    Check(bool),
+    Spin(i64),
+    String(String),
+    Button,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct EngineOption {
+    name: &'static str,
+    option_type: OptionType,
+    value: OptionValue,
+}
+
+impl EngineOption {
+    fn new(name: &'static str, option_type: OptionType) -> Self {
+        let value = match &option_type {
+            OptionType::Check { default } => OptionValue::Check(*default),
+            OptionType::Spin { default, .. } => OptionValue::Spin(*default),
+            OptionType::String { default } => OptionValue::String(default.clone()),
+            OptionType::Button => OptionValue::Button,
+        };
+        Self {
+            name,
+            option_type,
+            value,
+        }
+    }
+
+    fn to_uci_string(&self) -> String {
+        match &self.option_type {
+            OptionType::Check { default } => {
+                format!("option name {} type check default {}", self.name, default)
+            }
+            OptionType::Spin { default, min, max } => format!(
+                "option name {} type spin default {} min {} max {}",
+                self.name, default, min, max
+            ),
+            OptionType::String { default } if default.is_empty() => {
+                format!("option name {} type string default <empty>", self.name)
+            }
+            OptionType::String { default } => {
+                format!("option name {} type string default {}", self.name, default)
+            }
+            OptionType::Button => format!("option name {} type button", self.name),
+        }
+    }
+
+    fn parse_value(&self, value: Option<&str>) -> Result<OptionValue, UciError> {
+        match (&self.option_type, value) {
+            (OptionType::Check { .. }, Some("true")) => Ok(OptionValue::Check(true)),
+            (OptionType::Check { .. }, Some("false")) => Ok(OptionValue::Check(false)),
+            (OptionType::Spin { min, max, .. }, Some(value)) => {
+                let value = value
+                    .parse::<i64>()
+                    .map_err(|_| UciError::BadOptionFormat)?;
+                match (*min..=*max).contains(&value) {
+                    true => Ok(OptionValue::Spin(value)),
+                    false => Err(UciError::OptionOutOfRange),
+                }
+            }
+            (OptionType::String { .. }, Some("<empty>")) | (OptionType::String { .. }, None) => {
+                Ok(OptionValue::String(String::new()))
+            }
+            (OptionType::String { .. }, Some(value)) => Ok(OptionValue::String(value.to_string())),
+            (OptionType::Button, None) => Ok(OptionValue::Button),
+            _ => Err(UciError::BadOptionFormat),
+        }
+    }
+}
+
+// every option the engine advertises on `uci`, along with its current value
+pub struct Options(Vec<EngineOption>);
+
+impl Default for Options {
+    fn default() -> Self {
+        Self(vec![
+            EngineOption::new(
+                HASH,
+                OptionType::Spin {
+                    default: DEFAULT_HASH_SIZE as i64,
+                    min: MIN_HASH_SIZE as i64,
+                    max: MAX_HASH_SIZE as i64,
+                },
+            ),
+            EngineOption::new(CLEAR_HASH, OptionType::Button),
+            EngineOption::new(
+                THREADS,
+                OptionType::Spin {
+                    default: 1,
+                    min: 1,
+                    max: MAX_THREADS,
+                },
+            ),
+            EngineOption::new(
+                MULTI_PV,
+                OptionType::Spin {
+                    default: 1,
+                    min: 1,
+                    max: MAX_MULTI_PV,
+                },
+            ),
+            EngineOption::new(
+                MOVE_OVERHEAD,
+                OptionType::Spin {
+                    default: DEFAULT_MOVE_OVERHEAD.as_millis() as i64,
+                    min: 0,
+                    max: MAX_MOVE_OVERHEAD,
+                },
+            ),
+            // tells the gui it may send `go ponder`, the engine does not read it
+            EngineOption::new(PONDER, OptionType::Check { default: false }),
+            EngineOption::new(UCI_CHESS960, OptionType::Check { default: false }),
+            EngineOption::new(UCI_LIMIT_STRENGTH, OptionType::Check { default: false }),
+            EngineOption::new(
+                UCI_ELO,
+                OptionType::Spin {
+                    default: 1800,
+                    min: MIN_ELO,
+                    max: MAX_ELO,
+                },
+            ),
+        ])
+    }
+}
+
+impl Options {
+    pub fn to_uci_strings(&self) -> Vec<String> {
+        self.0.iter().map(|option| option.to_uci_string()).collect()
+    }
+
+    // option names are case insensitive, the registered name is returned so the caller can
+    // match on the constants above
+    pub fn set(&mut self, name: &str, value: Option<&str>) -> Result<&'static str, UciError> {
+        let option = self
+            .0
+            .iter_mut()
+            .find(|option| option.name.eq_ignore_ascii_case(name))
+            .ok_or(UciError::UnknownOption)?;
+        let value = option.parse_value(value)?;
+        // chess960 castling is not generated, the option only exists so the gui can turn it off
+        if option.name == UCI_CHESS960 && value == OptionValue::Check(true) {
+            return Err(UciError::UnsupportedOptionValue);
+        }
+        option.value = value;
+        Ok(option.name)
+    }
+
+    // the getters are only called with the constants above, anything else is a bug
+    pub fn get_spin(&self, name: &str) -> i64 {
+        match self.get_value(name) {
+            OptionValue::Spin(value) => *value,
+            value => panic!("{} is not a spin option: {:?}", name, value),
+        }
+    }
+
+    pub fn get_check(&self, name: &str) -> bool {
+        match self.get_value(name) {
+            OptionValue::Check(value) => *value,
+            value => panic!("{} is not a check option: {:?}", name, value),
+        }
+    }
+
+    fn get_value(&self, name: &str) -> &OptionValue {
+        match self.0.iter().find(|option| option.name == name) {
+            Some(option) => &option.value,
+            None => panic!("unknown option {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_to_uci_strings_tests() {
+        let options = Options::default().to_uci_strings();
+        assert_eq!(options.len(), 9);
+        assert!(options.contains(&String::from(
+            "option name Hash type spin default 16 min 1 max 65536"
+        )));
+        assert!(options.contains(&String::from("option name Clear Hash type button")));
+        assert!(options.contains(&String::from("option name Ponder type check default false")));
+        assert!(options.contains(&String::from(
+            "option name UCI_Elo type spin default 1800 min 1000 max 2600"
+        )));
+
+        let option = EngineOption::new(
+            "Book",
+            OptionType::String {
+                default: String::new(),
+            },
+        );
+        assert_eq!(
+            option.to_uci_string(),
+            "option name Book type string default <empty>"
+        );
+    }
+
+    #[test]
+    fn options_set_tests() {
+        let mut options = Options::default();
+
+        assert_eq!(options.set("threads", Some("4")), Ok(THREADS));
+        assert_eq!(options.get_spin(THREADS), 4);
+        assert_eq!(
+            options.set("Threads", Some("0")),
+            Err(UciError::OptionOutOfRange)
+        );
+        assert_eq!(
+            options.set("Threads", Some("two")),
+            Err(UciError::BadOptionFormat)
+        );
+        assert_eq!(options.get_spin(THREADS), 4);
+
+        assert_eq!(options.set("Ponder", Some("true")), Ok(PONDER));
+        assert!(options.get_check(PONDER));
+        assert_eq!(
+            options.set("Ponder", Some("yes")),
+            Err(UciError::BadOptionFormat)
+        );
+
+        assert_eq!(
+            options.set("UCI_Chess960", Some("true")),
+            Err(UciError::UnsupportedOptionValue)
+        );
+        assert!(!options.get_check(UCI_CHESS960));
+        assert_eq!(options.set("UCI_Chess960", Some("false")), Ok(UCI_CHESS960));
+        assert_eq!(
+            options.set("UCI_Elo", Some("900")),
+            Err(UciError::OptionOutOfRange)
+        );
+
+        assert_eq!(options.set("Clear Hash", None), Ok(CLEAR_HASH));
+        assert_eq!(
+            options.set("Clear Hash", Some("1")),
+            Err(UciError::BadOptionFormat)
+        );
+        assert_eq!(
+            options.set("Contempt", Some("0")),
+            Err(UciError::UnknownOption)
+        );
+    }
+}
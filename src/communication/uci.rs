@@ -1,8 +1,29 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, str::FromStr, time::Duration};
 
-use crate::{board::Board, defs::Promotion, move_generator::movelist::Move};
+use crate::{
+    board::{Board, ParseFenError},
+    constants::{ASCII_PIECE, SQUARE_NAME},
+    defs::{Promotion, Side},
+    move_generator::movelist::Move,
+    search::{SearchInfo, SearchLimits, MATE_SCORE, MATE_VALUE},
+};
+
+// depth used when a `go` command sets no limit at all
+pub const DEFAULT_GO_DEPTH: u32 = 6;
 
 impl Move {
+    pub fn to_uci_string(&self) -> String {
+        let mut uci_string = format!(
+            "{}{}",
+            SQUARE_NAME[self.get_source_square() as usize],
+            SQUARE_NAME[self.get_target_square() as usize]
+        );
+        if let Some(promotion) = self.get_promotion() {
+            uci_string.push_str(ASCII_PIECE[Side::Black as usize][promotion as usize]);
+        }
+        uci_string
+    }
+
     pub fn from_uci_string(uci_string: &str, board: &Board) -> Result<Move, UciError> {
         let move_list = board.generate_moves();
         let mut uci_iter = uci_string.chars();
@@ -38,7 +59,7 @@ impl Move {
         let source_square = source_file + source_rank * 8;
         let target_square = target_file + target_rank * 8;
 
-        let promotion = match uci_iter.next().map(|c| Promotion::try_from(c)) {
+        let promotion = match uci_iter.next().map(Promotion::try_from) {
             Some(r) => match r {
                 Ok(promotion) => Some(promotion),
                 Err(_) => return Err(UciError::BadMoveFormat),
@@ -49,10 +70,9 @@ impl Move {
         for mv in move_list {
             if (mv.get_source_square() == source_square)
                 && (mv.get_target_square() == target_square)
+                && (promotion == mv.get_promotion())
             {
-                if promotion == mv.get_promotion() {
-                    return Ok(mv);
-                }
+                return Ok(mv);
             }
         }
 
@@ -60,6 +80,62 @@ impl Move {
     }
 }
 
+impl SearchInfo {
+    pub fn to_uci_string(&self) -> String {
+        match self {
+            SearchInfo::Iteration {
+                depth,
+                multipv,
+                seldepth,
+                score,
+                nodes,
+                time,
+                hashfull,
+                pv,
+            } => {
+                let millis = time.as_millis() as u64;
+                let nps = nodes * 1000 / millis.max(1);
+                let mut uci_string = format!(
+                    "info depth {} seldepth {} multipv {} score {} nodes {} nps {} hashfull {} time {}",
+                    depth,
+                    seldepth,
+                    multipv,
+                    score_to_uci_string(*score),
+                    nodes,
+                    nps,
+                    hashfull,
+                    millis
+                );
+                if !pv.is_empty() {
+                    uci_string.push_str(" pv");
+                    for mv in pv.iter() {
+                        uci_string.push(' ');
+                        uci_string.push_str(&mv.to_uci_string());
+                    }
+                }
+                uci_string
+            }
+            SearchInfo::CurrentMove { depth, mv, number } => format!(
+                "info depth {} currmove {} currmovenumber {}",
+                depth,
+                mv.to_uci_string(),
+                number
+            ),
+        }
+    }
+}
+
+// mate scores are given in moves, negative when the engine is getting mated
+fn score_to_uci_string(score: i32) -> String {
+    if score > MATE_SCORE {
+        format!("mate {}", (MATE_VALUE - score + 1) / 2)
+    } else if score < -MATE_SCORE {
+        format!("mate {}", -((MATE_VALUE + score) / 2))
+    } else {
+        format!("cp {}", score)
+    }
+}
+
 pub fn parse_uci_position(uci_string: &str) -> Result<Board, UciError> {
     enum Tokens {
         Nothing,
@@ -96,7 +172,7 @@ pub fn parse_uci_position(uci_string: &str) -> Result<Board, UciError> {
         true => Board::default(),
         false => match Board::from_fen(fen.as_str()) {
             Ok(b) => b,
-            Err(_) => return Err(UciError::BadPositionFormat),
+            Err(err) => return Err(UciError::BadPositionFormat(err)),
         },
     };
 
@@ -105,64 +181,147 @@ pub fn parse_uci_position(uci_string: &str) -> Result<Board, UciError> {
             Ok(mv) => mv,
             Err(_) => return Err(UciError::BadMoveFormat),
         };
-        match board.make_move(mv, false) {
-            Err(_) => return Err(UciError::IllegalMove),
-            _ => {}
-        };
+        if board.make_move(mv, false).is_err() {
+            return Err(UciError::IllegalMove);
+        }
     }
     Ok(board)
 }
 
-pub fn parse_uci_go(uci_string: &str, board: &Board) -> Result<Move, UciError> {
+pub fn parse_uci_go(uci_string: &str, board: &Board) -> Result<SearchLimits, UciError> {
     enum Tokens {
         Nothing,
         Depth,
+        Nodes,
+        Mate,
+        MoveTime,
+        WTime,
+        BTime,
+        WInc,
+        BInc,
+        MovesToGo,
+        SearchMoves,
     }
 
-    let parts: Vec<String> = uci_string
-        .split_whitespace()
-        .map(|s| s.to_string())
-        .collect();
-
-    let mut depth: Option<u32> = None;
+    let mut limits = SearchLimits::default();
     let mut token = Tokens::Nothing;
 
-    for p in parts {
+    for p in uci_string.split_whitespace() {
         match p {
-            t if t == "depth" => token = Tokens::Depth,
+            "go" => (),
+            "depth" => token = Tokens::Depth,
+            "nodes" => token = Tokens::Nodes,
+            "mate" => token = Tokens::Mate,
+            "movetime" => token = Tokens::MoveTime,
+            "wtime" => token = Tokens::WTime,
+            "btime" => token = Tokens::BTime,
+            "winc" => token = Tokens::WInc,
+            "binc" => token = Tokens::BInc,
+            "movestogo" => token = Tokens::MovesToGo,
+            "searchmoves" => token = Tokens::SearchMoves,
+            "infinite" => {
+                limits.infinite = true;
+                token = Tokens::Nothing;
+            }
+            "ponder" => {
+                limits.ponder = true;
+                token = Tokens::Nothing;
+            }
             _ => match token {
-                Tokens::Depth => {
-                    depth = match p.parse::<u32>() {
-                        Ok(d) => Some(d),
-                        Err(_) => return Err(UciError::BadGoFormat),
-                    };
+                Tokens::Depth => limits.depth = Some(parse_go_value(p)?),
+                Tokens::Nodes => limits.nodes = Some(parse_go_value(p)?),
+                Tokens::Mate => limits.mate = Some(parse_go_value(p)?),
+                Tokens::MoveTime => limits.movetime = Some(parse_go_time(p)?),
+                Tokens::WTime => limits.wtime = Some(parse_go_time(p)?),
+                Tokens::BTime => limits.btime = Some(parse_go_time(p)?),
+                Tokens::WInc => limits.winc = Some(parse_go_time(p)?),
+                Tokens::BInc => limits.binc = Some(parse_go_time(p)?),
+                Tokens::MovesToGo => limits.movestogo = Some(parse_go_value(p)?),
+                Tokens::SearchMoves => {
+                    let mv = Move::from_uci_string(p, board)?;
+                    if !board.generate_legal_moves().contains(mv) {
+                        return Err(UciError::IllegalMove);
+                    }
+                    limits.searchmoves.push(mv);
                 }
-
                 Tokens::Nothing => (),
             },
         }
     }
 
-    match depth {
-        Some(d) => {
-            let mv = board.search(d);
-            match mv {
-                Some(m) => return Ok(m),
-                None => return Err(UciError::NoAvailableMove),
-            }
+    // only the clock of the side to move limits the search
+    let clock = match board.get_state().side_to_move {
+        Side::White => limits.wtime,
+        Side::Black => limits.btime,
+    };
+    let has_limit = limits.depth.is_some()
+        || limits.nodes.is_some()
+        || limits.mate.is_some()
+        || limits.movetime.is_some()
+        || clock.is_some()
+        || limits.infinite;
+    if !has_limit {
+        limits.depth = Some(DEFAULT_GO_DEPTH);
+    }
+    Ok(limits)
+}
+
+fn parse_go_value<T: FromStr>(value: &str) -> Result<T, UciError> {
+    value.parse::<T>().map_err(|_| UciError::BadGoFormat)
+}
+
+// some guis send a negative time once the clock has run out
+fn parse_go_time(value: &str) -> Result<Duration, UciError> {
+    let ms = parse_go_value::<i64>(value)?;
+    Ok(Duration::from_millis(ms.max(0) as u64))
+}
+
+// returns the option name and its value, if any
+pub fn parse_uci_setoption(uci_string: &str) -> Result<(String, Option<String>), UciError> {
+    enum Tokens {
+        Nothing,
+        Name,
+        Value,
+    }
+
+    let mut name: Vec<&str> = Vec::new();
+    let mut value: Vec<&str> = Vec::new();
+    let mut token = Tokens::Nothing;
+
+    // option names and values may contain spaces
+    for p in uci_string.split_whitespace() {
+        match p {
+            "setoption" => (),
+            "name" => token = Tokens::Name,
+            "value" => token = Tokens::Value,
+            _ => match token {
+                Tokens::Nothing => return Err(UciError::BadOptionFormat),
+                Tokens::Name => name.push(p),
+                Tokens::Value => value.push(p),
+            },
         }
-        None => {}
     }
-    Err(UciError::BadGoFormat)
+
+    if name.is_empty() {
+        return Err(UciError::BadOptionFormat);
+    }
+    match value.is_empty() {
+        true => Ok((name.join(" "), None)),
+        false => Ok((name.join(" "), Some(value.join(" ")))),
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub enum UciError {
     BadMoveFormat,
     BadGoFormat,
-    BadPositionFormat,
+    BadPositionFormat(ParseFenError),
+    BadOptionFormat,
+    OptionOutOfRange,
+    UnknownOption,
+    // a value the option accepts in the protocol, but the engine does not support
+    UnsupportedOptionValue,
     IllegalMove,
-    NoAvailableMove,
 }
 
 #[cfg(test)]
@@ -171,6 +330,17 @@ mod tests {
     use crate::constants::*;
     use crate::defs::*;
 
+    #[test]
+    fn move_to_uci_string_tests() {
+        let mv = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+        assert_eq!(mv.to_uci_string(), "e2e4");
+        #[rustfmt::skip]
+        let mv = Move::new(D2, D1, Piece::Pawn, Some(Promotion::Knight), false, false, false, false);
+        assert_eq!(mv.to_uci_string(), "d2d1n");
+        let mv = Move::new(E8, G8, Piece::King, None, false, false, false, true);
+        assert_eq!(mv.to_uci_string(), "e8g8");
+    }
+
     #[test]
     fn move_from_uci_string_tests() {
         let board = Board::default();
@@ -181,10 +351,10 @@ mod tests {
         assert_eq!(mv.get_target_square(), E4);
         assert_eq!(mv.get_piece(), Piece::Pawn);
         assert_eq!(mv.get_promotion(), None);
-        assert_eq!(mv.get_capture(), false);
-        assert_eq!(mv.get_double_push(), true);
-        assert_eq!(mv.get_en_passant(), false);
-        assert_eq!(mv.get_castling(), false);
+        assert!(!mv.get_capture());
+        assert!(mv.get_double_push());
+        assert!(!mv.get_en_passant());
+        assert!(!mv.get_castling());
 
         let uci_string = "e3e4";
         let mv = Move::from_uci_string(uci_string, &board);
@@ -205,10 +375,10 @@ mod tests {
         assert_eq!(mv.get_target_square(), D1);
         assert_eq!(mv.get_piece(), Piece::Pawn);
         assert_eq!(mv.get_promotion(), Some(Promotion::Queen));
-        assert_eq!(mv.get_capture(), false);
-        assert_eq!(mv.get_double_push(), false);
-        assert_eq!(mv.get_en_passant(), false);
-        assert_eq!(mv.get_castling(), false);
+        assert!(!mv.get_capture());
+        assert!(!mv.get_double_push());
+        assert!(!mv.get_en_passant());
+        assert!(!mv.get_castling());
 
         let uci_string = "d2d1r";
         let mv = Move::from_uci_string(uci_string, &board).unwrap();
@@ -216,11 +386,120 @@ mod tests {
         assert_eq!(mv.get_target_square(), D1);
         assert_eq!(mv.get_piece(), Piece::Pawn);
         assert_eq!(mv.get_promotion(), Some(Promotion::Rook));
-        assert_eq!(mv.get_capture(), false);
-        assert_eq!(mv.get_double_push(), false);
-        assert_eq!(mv.get_en_passant(), false);
-        assert_eq!(mv.get_castling(), false);
+        assert!(!mv.get_capture());
+        assert!(!mv.get_double_push());
+        assert!(!mv.get_en_passant());
+        assert!(!mv.get_castling());
+    }
+    #[test]
+    fn search_info_to_uci_string_tests() {
+        let info = SearchInfo::Iteration {
+            depth: 2,
+            multipv: 1,
+            seldepth: 5,
+            score: 35,
+            nodes: 1500,
+            time: Duration::from_millis(500),
+            hashfull: 12,
+            pv: vec![
+                Move::new(E2, E4, Piece::Pawn, None, false, true, false, false),
+                Move::new(E7, E5, Piece::Pawn, None, false, true, false, false),
+            ],
+        };
+        assert_eq!(
+            info.to_uci_string(),
+            "info depth 2 seldepth 5 multipv 1 score cp 35 nodes 1500 nps 3000 hashfull 12 time 500 pv e2e4 e7e5"
+        );
+
+        let info = SearchInfo::CurrentMove {
+            depth: 9,
+            mv: Move::new(G1, F3, Piece::Knight, None, false, false, false, false),
+            number: 4,
+        };
+        assert_eq!(
+            info.to_uci_string(),
+            "info depth 9 currmove g1f3 currmovenumber 4"
+        );
+
+        assert_eq!(score_to_uci_string(-120), "cp -120");
+        assert_eq!(score_to_uci_string(MATE_VALUE - 1), "mate 1");
+        assert_eq!(score_to_uci_string(MATE_VALUE - 3), "mate 2");
+        assert_eq!(score_to_uci_string(-MATE_VALUE + 2), "mate -1");
+    }
+
+    #[test]
+    fn parse_uci_go_tests() {
+        let board = Board::default();
+
+        let limits = parse_uci_go("go", &board).unwrap();
+        assert_eq!(limits.depth, Some(DEFAULT_GO_DEPTH));
+
+        let limits = parse_uci_go(
+            "go wtime 300000 btime -20 winc 2000 binc 2000 movestogo 40",
+            &board,
+        )
+        .unwrap();
+        assert_eq!(limits.depth, None);
+        assert_eq!(limits.wtime, Some(Duration::from_secs(300)));
+        assert_eq!(limits.btime, Some(Duration::from_secs(0)));
+        assert_eq!(limits.winc, Some(Duration::from_secs(2)));
+        assert_eq!(limits.binc, Some(Duration::from_secs(2)));
+        assert_eq!(limits.movestogo, Some(40));
+
+        let limits = parse_uci_go("go ponder infinite searchmoves e2e4 d2d4", &board).unwrap();
+        assert!(limits.ponder);
+        assert!(limits.infinite);
+        #[rustfmt::skip]
+        assert_eq!(limits.searchmoves, vec![
+            Move::new(E2, E4, Piece::Pawn, None, false, true, false, false),
+            Move::new(D2, D4, Piece::Pawn, None, false, true, false, false),
+        ]);
+
+        let limits = parse_uci_go("go nodes 5000 mate 3 movetime 100", &board).unwrap();
+        assert_eq!(limits.nodes, Some(5000));
+        assert_eq!(limits.mate, Some(3));
+        assert_eq!(limits.movetime, Some(Duration::from_millis(100)));
+
+        assert_eq!(
+            parse_uci_go("go depth x", &board),
+            Err(UciError::BadGoFormat)
+        );
+        assert_eq!(
+            parse_uci_go("go searchmoves e2e5", &board),
+            Err(UciError::IllegalMove)
+        );
+
+        // the bishop is pinned, and black has no clock of its own
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            parse_uci_go("go searchmoves e2d3", &board),
+            Err(UciError::IllegalMove)
+        );
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 b - - 0 1").unwrap();
+        let limits = parse_uci_go("go wtime 1000", &board).unwrap();
+        assert_eq!(limits.depth, Some(DEFAULT_GO_DEPTH));
+    }
+
+    #[test]
+    fn parse_uci_setoption_tests() {
+        assert_eq!(
+            parse_uci_setoption("setoption name Hash value 64"),
+            Ok((String::from("Hash"), Some(String::from("64"))))
+        );
+        assert_eq!(
+            parse_uci_setoption("setoption name Clear Hash"),
+            Ok((String::from("Clear Hash"), None))
+        );
+        assert_eq!(
+            parse_uci_setoption("setoption value 64"),
+            Err(UciError::BadOptionFormat)
+        );
+        assert_eq!(
+            parse_uci_setoption("setoption Hash 64"),
+            Err(UciError::BadOptionFormat)
+        );
     }
+
     #[test]
     fn parse_uci_position_tests() {
         let uci_position = "position startpos";
@@ -8,9 +8,9 @@ pub enum Side {
     Black = 1,
 }
 
-impl Into<usize> for Side {
-    fn into(self) -> usize {
-        self as usize
+impl From<Side> for usize {
+    fn from(side: Side) -> Self {
+        side as usize
     }
 }
 
@@ -65,6 +65,17 @@ pub enum Promotion {
     Rook = 5,
 }
 
+impl From<Promotion> for Piece {
+    fn from(promotion: Promotion) -> Self {
+        match promotion {
+            Promotion::Queen => Piece::Queen,
+            Promotion::Knight => Piece::Knight,
+            Promotion::Bishop => Piece::Bishop,
+            Promotion::Rook => Piece::Rook,
+        }
+    }
+}
+
 impl TryFrom<u32> for Promotion {
     type Error = &'static str;
 
@@ -149,6 +160,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::erasing_op)]
     fn castle_bitwise_and_tests() {
         let castle_rights = CastleRights::Both;
 
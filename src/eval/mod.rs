@@ -0,0 +1,101 @@
+mod psqt;
+
+use crate::{board::BoardState, defs::Side};
+
+use self::psqt::{ENDGAME_TABLES, MIDDLEGAME_TABLES};
+
+// material values indexed by Piece, the king is never captured so it is worth nothing
+pub const MIDDLEGAME_MATERIAL: [i32; 6] = [0, 1025, 82, 337, 365, 477];
+pub const ENDGAME_MATERIAL: [i32; 6] = [0, 936, 94, 281, 297, 512];
+
+// contribution of each piece to the game phase, a full set of pieces adds up to MAX_PHASE
+const PHASE_WEIGHTS: [i32; 6] = [0, 4, 0, 1, 1, 2];
+pub const MAX_PHASE: i32 = 24;
+
+// scores the position from the side to move point of view, in centipawns
+pub fn evaluate(state: &BoardState) -> i32 {
+    let mut middlegame = [0; 2];
+    let mut endgame = [0; 2];
+
+    for side in [Side::White, Side::Black] {
+        for (piece, bitboard) in state.bitboards[side as usize].iter().enumerate() {
+            for square in bitboard.into_iter() {
+                let index = match side {
+                    Side::White => square ^ 56,
+                    Side::Black => square,
+                } as usize;
+
+                middlegame[side as usize] +=
+                    MIDDLEGAME_MATERIAL[piece] + MIDDLEGAME_TABLES[piece][index];
+                endgame[side as usize] += ENDGAME_MATERIAL[piece] + ENDGAME_TABLES[piece][index];
+            }
+        }
+    }
+
+    let side = state.side_to_move as usize;
+    let opposite = state.side_to_move.get_opposite_side() as usize;
+    let middlegame_score = middlegame[side] - middlegame[opposite];
+    let endgame_score = endgame[side] - endgame[opposite];
+
+    // blend both scores, early promotions can push the phase past its maximum
+    let phase = game_phase(state).min(MAX_PHASE);
+    (middlegame_score * phase + endgame_score * (MAX_PHASE - phase)) / MAX_PHASE
+}
+
+// MAX_PHASE with every piece on the board, 0 when only kings and pawns are left
+pub fn game_phase(state: &BoardState) -> i32 {
+    state
+        .bitboards
+        .iter()
+        .flat_map(|bitboards| bitboards.iter().zip(PHASE_WEIGHTS.iter()))
+        .map(|(bitboard, weight)| bitboard.count_occupied_squares() as i32 * weight)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    fn evaluate_fen(fen: &str) -> i32 {
+        evaluate(Board::from_fen(fen).unwrap().get_state())
+    }
+
+    #[test]
+    fn evaluate_initial_position_tests() {
+        let board = Board::default();
+        assert_eq!(evaluate(board.get_state()), 0);
+        assert_eq!(game_phase(board.get_state()), MAX_PHASE);
+    }
+
+    #[test]
+    fn evaluate_side_to_move_tests() {
+        // white is a queen up
+        let white_to_move =
+            evaluate_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let black_to_move =
+            evaluate_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1");
+        assert!(white_to_move > 800);
+        assert_eq!(white_to_move, -black_to_move);
+    }
+
+    #[test]
+    fn evaluate_mirrored_position_tests() {
+        let white =
+            evaluate_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let black =
+            evaluate_fen("rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3");
+        assert_eq!(white, black);
+    }
+
+    #[test]
+    fn evaluate_tapered_tests() {
+        let board = Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+        assert_eq!(game_phase(board.get_state()), 0);
+
+        // a king in the center is bad in the middlegame but good in the endgame
+        let centralised = evaluate_fen("8/8/8/3k4/8/8/8/R3K3 b - - 0 1");
+        let cornered = evaluate_fen("7k/8/8/8/8/8/8/R3K3 b - - 0 1");
+        assert!(centralised > cornered);
+    }
+}
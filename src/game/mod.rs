@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use crate::{
+    board::{Board, MakeMoveError},
+    move_generator::movelist::Move,
+};
+
+pub type NodeId = usize;
+
+// the node holding the starting position, it has no move
+pub const ROOT: NodeId = 0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameNode {
+    mv: Option<Move>,
+    parent: Option<NodeId>,
+    // the first child continues the line, the others are variations replacing it
+    children: Vec<NodeId>,
+    // text following the move, the root comments come before the first move
+    pub comments: Vec<String>,
+    // numeric annotation glyphs, $1 for `!`, $2 for `?` and so on
+    pub nags: Vec<u8>,
+    // time left on the clock of the side that played the move
+    pub clock: Option<Duration>,
+}
+
+impl GameNode {
+    fn new(mv: Option<Move>, parent: Option<NodeId>) -> Self {
+        Self {
+            mv,
+            parent,
+            children: vec![],
+            comments: vec![],
+            nags: vec![],
+            clock: None,
+        }
+    }
+
+    pub fn get_move(&self) -> Option<Move> {
+        self.mv
+    }
+
+    pub fn get_parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    pub fn get_children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+// a starting position and the tree of every move played or analysed from it
+#[derive(Debug, Clone)]
+pub struct Game {
+    start: Board,
+    nodes: Vec<GameNode>,
+    // node being looked at and its position
+    cursor: NodeId,
+    board: Board,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Board::default())
+    }
+}
+
+impl Game {
+    pub fn new(start: Board) -> Self {
+        Self {
+            board: start.clone(),
+            start,
+            nodes: vec![GameNode::new(None, None)],
+            cursor: ROOT,
+        }
+    }
+
+    pub fn get_start(&self) -> &Board {
+        &self.start
+    }
+
+    pub fn get_node(&self, id: NodeId) -> &GameNode {
+        &self.nodes[id]
+    }
+
+    pub fn get_node_mut(&mut self, id: NodeId) -> &mut GameNode {
+        &mut self.nodes[id]
+    }
+
+    // adding a move already in the tree returns its node, an illegal move leaves the tree
+    // untouched
+    pub fn add_move(&mut self, parent: NodeId, mv: Move) -> Result<NodeId, MakeMoveError> {
+        if !self.get_board(parent).generate_legal_moves().contains(mv) {
+            return Err(MakeMoveError::IllegalMove(mv));
+        }
+        Ok(self.add_node(parent, mv))
+    }
+
+    // the move must be legal after `parent`
+    fn add_node(&mut self, parent: NodeId, mv: Move) -> NodeId {
+        if let Some(id) = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|id| self.nodes[*id].mv == Some(mv))
+        {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(GameNode::new(Some(mv), Some(parent)));
+        self.nodes[parent].children.push(id);
+        id
+    }
+
+    pub fn get_mainline(&self) -> Vec<Move> {
+        let mut moves = vec![];
+        let mut id = ROOT;
+        while let Some(next) = self.nodes[id].children.first() {
+            id = *next;
+            moves.extend(self.nodes[id].mv);
+        }
+        moves
+    }
+
+    // moves from the starting position down to `id`
+    pub fn get_moves_to(&self, id: NodeId) -> Vec<Move> {
+        let mut moves = vec![];
+        let mut node = &self.nodes[id];
+        while let Some(parent) = node.parent {
+            moves.extend(node.mv);
+            node = &self.nodes[parent];
+        }
+        moves.reverse();
+        moves
+    }
+
+    pub fn get_board(&self, id: NodeId) -> Board {
+        let mut board = self.start.clone();
+        for mv in self.get_moves_to(id) {
+            board
+                .make_move(mv, false)
+                .expect("moves are checked when they enter the tree");
+        }
+        board
+    }
+
+    pub fn get_cursor(&self) -> NodeId {
+        self.cursor
+    }
+
+    pub fn get_current_board(&self) -> &Board {
+        &self.board
+    }
+
+    // number of moves played from the starting position to reach `id`
+    pub fn get_ply(&self, id: NodeId) -> usize {
+        let mut ply = 0;
+        let mut node = &self.nodes[id];
+        while let Some(parent) = node.parent {
+            ply += 1;
+            node = &self.nodes[parent];
+        }
+        ply
+    }
+
+    // plays a move after the cursor and moves onto it, an illegal move leaves the game untouched
+    pub fn play(&mut self, mv: Move) -> Result<NodeId, MakeMoveError> {
+        if !self.board.generate_legal_moves().contains(mv) {
+            return Err(MakeMoveError::IllegalMove(mv));
+        }
+        self.board.make_move(mv, false)?;
+        self.cursor = self.add_node(self.cursor, mv);
+        Ok(self.cursor)
+    }
+
+    // follows the line of the cursor, false at the end of it
+    pub fn forward(&mut self) -> bool {
+        match self.nodes[self.cursor].children.first().copied() {
+            Some(next) => {
+                if let Some(mv) = self.nodes[next].mv {
+                    self.board
+                        .make_move(mv, false)
+                        .expect("moves are checked when they enter the tree");
+                }
+                self.cursor = next;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // false at the starting position
+    pub fn back(&mut self) -> bool {
+        match self.nodes[self.cursor].parent {
+            Some(parent) => {
+                self.board.take_back_move();
+                self.cursor = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn go_to(&mut self, id: NodeId) {
+        self.board = self.get_board(id);
+        self.cursor = id;
+    }
+
+    // goes back along the current line or forward along its continuation, false if the line is
+    // shorter than `ply`, in which case the cursor stops at its end
+    pub fn jump_to_ply(&mut self, ply: usize) -> bool {
+        let mut current = self.get_ply(self.cursor);
+        while current > ply {
+            self.back();
+            current -= 1;
+        }
+        while current < ply {
+            if !self.forward() {
+                return false;
+            }
+            current += 1;
+        }
+        true
+    }
+
+    // makes `id` the continuation of its parent, the former one becomes a variation
+    pub fn promote_variation(&mut self, id: NodeId) -> bool {
+        match self.nodes[id].parent {
+            Some(parent) => {
+                let children = &mut self.nodes[parent].children;
+                if let Some(index) = children.iter().position(|child| *child == id) {
+                    children.remove(index);
+                    children.insert(0, id);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::*, defs::Piece};
+
+    #[test]
+    fn game_tree_tests() {
+        let e4 = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+        let d4 = Move::new(D2, D4, Piece::Pawn, None, false, true, false, false);
+        let e5 = Move::new(E7, E5, Piece::Pawn, None, false, true, false, false);
+
+        let mut game = Game::default();
+        let e4_node = game.add_move(ROOT, e4).unwrap();
+        let e5_node = game.add_move(e4_node, e5).unwrap();
+        let d4_node = game.add_move(ROOT, d4).unwrap();
+        assert_eq!(game.add_move(ROOT, e4).unwrap(), e4_node);
+        assert!(matches!(
+            game.add_move(e5_node, e5),
+            Err(MakeMoveError::IllegalMove(mv)) if mv == e5
+        ));
+
+        assert_eq!(game.get_node(ROOT).get_children(), &[e4_node, d4_node]);
+        assert_eq!(game.get_node(e5_node).get_parent(), Some(e4_node));
+        assert_eq!(game.get_mainline(), vec![e4, e5]);
+        assert_eq!(game.get_moves_to(d4_node), vec![d4]);
+
+        let mut board = Board::default();
+        board.make_move(e4, false).unwrap();
+        board.make_move(e5, false).unwrap();
+        assert_eq!(game.get_board(e5_node), board);
+    }
+
+    #[test]
+    fn game_navigation_tests() {
+        let e4 = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+        let e5 = Move::new(E7, E5, Piece::Pawn, None, false, true, false, false);
+        let c5 = Move::new(C7, C5, Piece::Pawn, None, false, true, false, false);
+        let nf3 = Move::new(G1, F3, Piece::Knight, None, false, false, false, false);
+
+        let mut game = Game::default();
+        let e4_node = game.play(e4).unwrap();
+        let e5_node = game.play(e5).unwrap();
+        assert!(game.play(e5).is_err());
+        assert_eq!(game.get_cursor(), e5_node);
+
+        assert!(game.back());
+        let c5_node = game.play(c5).unwrap();
+        game.play(nf3).unwrap();
+        assert_eq!(game.get_mainline(), vec![e4, e5]);
+        assert!(game.promote_variation(c5_node));
+        assert!(!game.promote_variation(ROOT));
+        assert_eq!(game.get_mainline(), vec![e4, c5, nf3]);
+        assert_eq!(game.get_node(e4_node).get_children(), &[c5_node, e5_node]);
+
+        assert!(game.jump_to_ply(0));
+        assert_eq!(game.get_current_board(), &Board::default());
+        assert!(!game.back());
+        assert!(!game.jump_to_ply(4));
+        assert_eq!(game.get_ply(game.get_cursor()), 3);
+        assert!(!game.forward());
+
+        game.go_to(e5_node);
+        assert_eq!(game.get_current_board(), &game.get_board(e5_node));
+        assert!(game.jump_to_ply(1));
+        assert_eq!(game.get_cursor(), e4_node);
+        assert!(game.forward());
+        assert_eq!(game.get_cursor(), c5_node);
+        assert_eq!(game.get_current_board(), &game.get_board(c5_node));
+    }
+}
@@ -3,7 +3,11 @@ pub mod board;
 pub mod communication;
 pub mod constants;
 pub mod defs;
+pub mod eval;
+pub mod game;
 pub mod magic;
 mod mov;
 pub mod move_generator;
 pub mod perft;
+pub mod pgn;
+pub mod search;
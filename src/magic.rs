@@ -147,10 +147,7 @@ pub const BISHOP_TABLE_SIZE: usize = 5_248; // Total permutations of all bishop
 
 pub fn find_magics(piece: Piece) {
     // Create working variables.
-    let is_rook = match piece {
-        Piece::Rook => true,
-        _ => false,
-    };
+    let is_rook = matches!(piece, Piece::Rook);
     let mut table = match piece {
         Piece::Rook => vec![Bitboard::default(); ROOK_TABLE_SIZE],
         Piece::Bishop => vec![Bitboard::default(); BISHOP_TABLE_SIZE],
@@ -277,7 +274,7 @@ fn found_magic(square: Square, m: Magic, offset: u64, end: u64, attempts: u64) {
     );
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct Magic {
     pub mask: Bitboard,
     pub shift: u8,
@@ -285,17 +282,6 @@ pub struct Magic {
     pub nr: u64,
 }
 
-impl Default for Magic {
-    fn default() -> Self {
-        Self {
-            mask: Bitboard::default(),
-            shift: 0,
-            offset: 0,
-            nr: 0,
-        }
-    }
-}
-
 impl Magic {
     pub fn get_index(&self, occupancy: Bitboard) -> usize {
         let blockerboard = occupancy & self.mask;
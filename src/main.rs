@@ -0,0 +1,8 @@
+use std::io;
+
+use knightime::communication::engine::Engine;
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    Engine::default().run(stdin.lock(), io::stdout())
+}
@@ -90,7 +90,7 @@ impl MoveGenerator {
             }
         }
 
-        return attacks;
+        attacks
     }
 }
 
@@ -58,18 +58,18 @@ impl MoveGenerator {
                 ),
             };
 
-            let mut magic: Magic = Default::default();
-
-            magic.mask = mask;
-            magic.shift = (64 - bits) as u8;
-            magic.offset = offset;
-            magic.nr = match piece {
-                Piece::Bishop => BISHOP_MAGIC_NUMBERS[sq as usize],
-                Piece::Rook => ROOK_MAGIC_NUMBERS[sq as usize],
-                _ => panic!(
-                    "can only generate magic for rook or bishop, {:?} was passed.",
-                    piece
-                ),
+            let magic = Magic {
+                mask,
+                shift: (64 - bits) as u8,
+                offset,
+                nr: match piece {
+                    Piece::Bishop => BISHOP_MAGIC_NUMBERS[sq as usize],
+                    Piece::Rook => ROOK_MAGIC_NUMBERS[sq as usize],
+                    _ => panic!(
+                        "can only generate magic for rook or bishop, {:?} was passed.",
+                        piece
+                    ),
+                },
             };
 
             for i in 0..permutations {
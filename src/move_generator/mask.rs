@@ -22,7 +22,7 @@ impl MoveGenerator {
                 attacks |= move_sw(bitboard) | move_se(bitboard);
             }
         }
-        return attacks;
+        attacks
     }
 
     pub fn mask_knight_attacks(square: Square) -> Bitboard {
@@ -57,7 +57,7 @@ impl MoveGenerator {
             attacks |= bitboard >> 6u32;
         }
 
-        return attacks;
+        attacks
     }
 
     pub fn mask_king_attacks(square: Square) -> Bitboard {
@@ -68,7 +68,7 @@ impl MoveGenerator {
         bitboard |= attacks;
         attacks |= move_n(bitboard) | move_s(bitboard);
 
-        return attacks;
+        attacks
     }
 
     pub fn mask_rook_attacks(square: Square) -> Bitboard {
@@ -79,7 +79,7 @@ impl MoveGenerator {
         let target_rank = square / 8;
         let target_file = square % 8;
 
-        let mut attacks = Bitboard::new((FILE_A << target_file) | (RANK_1 << target_rank * 8));
+        let mut attacks = Bitboard::new((FILE_A << target_file) | (RANK_1 << (target_rank * 8)));
 
         attacks &= !bitboard
             & !(bitboard >> target_file)
@@ -102,16 +102,16 @@ impl MoveGenerator {
         let offset_ha: i32 = target_file - (8 - target_rank) + 1;
 
         let mut attacks = match offset_ah.cmp(&0i32) {
-            std::cmp::Ordering::Less => Bitboard::new(DIAGONAL_AH << offset_ah.abs() * 8),
-            _ => Bitboard::new(DIAGONAL_AH >> offset_ah * 8),
+            std::cmp::Ordering::Less => Bitboard::new(DIAGONAL_AH << (offset_ah.abs() * 8)),
+            _ => Bitboard::new(DIAGONAL_AH >> (offset_ah * 8)),
         };
 
         match offset_ha.cmp(&0i32) {
-            std::cmp::Ordering::Less => attacks |= DIAGONAL_HA >> offset_ha.abs() * 8,
-            _ => attacks |= DIAGONAL_HA << offset_ha * 8,
+            std::cmp::Ordering::Less => attacks |= DIAGONAL_HA >> (offset_ha.abs() * 8),
+            _ => attacks |= DIAGONAL_HA << (offset_ha * 8),
         };
 
-        return attacks & !bitboard & !FILE_A & !FILE_H & !RANK_1 & !RANK_8;
+        attacks & !bitboard & !FILE_A & !FILE_H & !RANK_1 & !RANK_8
     }
 }
 
@@ -1,4 +1,4 @@
-use std::usize;
+use std::sync::OnceLock;
 
 use crate::{
     bitboard::Bitboard,
@@ -24,6 +24,15 @@ pub struct MoveGenerator {
     bishop_magics: [Magic; 64],
 }
 
+// built on first use and shared by every board of the process
+static MOVE_GENERATOR: OnceLock<MoveGenerator> = OnceLock::new();
+
+impl Default for MoveGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MoveGenerator {
     pub fn new() -> MoveGenerator {
         let magics: Magic = Magic::default();
@@ -45,6 +54,10 @@ impl MoveGenerator {
         move_generator
     }
 
+    pub fn instance() -> &'static MoveGenerator {
+        MOVE_GENERATOR.get_or_init(MoveGenerator::new)
+    }
+
     pub fn get_pawn_attacks(&self, square: Square, side: Side) -> Bitboard {
         match side {
             Side::White => self.white_pawns[square as usize],
@@ -128,4 +141,13 @@ mod tests {
 
         assert_eq!(movgen.get_knight_attacks(D4), 0x0000142200221400);
     }
+    #[test]
+    fn instance_tests() {
+        // every board shares the same tables
+        assert!(std::ptr::eq(
+            MoveGenerator::instance(),
+            MoveGenerator::instance()
+        ));
+        assert!(*MoveGenerator::instance() == MoveGenerator::new());
+    }
 }
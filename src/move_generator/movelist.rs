@@ -26,18 +26,19 @@ impl IntoIterator for MoveList {
     }
 }
 
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MoveList {
     pub fn new() -> Self {
         Self(vec![])
     }
 
     pub fn get(&self, index: usize) -> Option<Move> {
-        let el = self.0.get(index);
-
-        match el {
-            Some(e) => return Some(e.clone()),
-            None => return None,
-        }
+        self.0.get(index).copied()
     }
 
     pub fn add_move(&mut self, mv: Move) {
@@ -55,6 +56,45 @@ impl MoveList {
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScoredMove {
+    pub mv: Move,
+    pub score: i32,
+}
+
+#[derive(Debug, Default)]
+pub struct ScoredMoveList(Vec<ScoredMove>);
+
+impl ScoredMoveList {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    pub fn add_move(&mut self, mv: Move, score: i32) {
+        self.0.push(ScoredMove { mv, score })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // one step of a selection sort : the best move left after `index` is swapped into it.
+    // most nodes cut off after a few moves so sorting the whole list would be wasted work
+    pub fn pick_best(&mut self, index: usize) -> Option<ScoredMove> {
+        let best = (index..self.0.len()).max_by_key(|i| self.0[*i].score)?;
+        self.0.swap(index, best);
+        Some(self.0[index])
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -73,11 +113,8 @@ impl fmt::Display for Move {
             write!(f, "x")?;
         }
         write!(f, "{}", SQUARE_NAME[self.get_target_square() as usize])?;
-        match self.get_promotion() {
-            Some(promotion) => {
-                write!(f, "={}", UNICODE_PIECE[0][promotion as usize])?;
-            }
-            None => {}
+        if let Some(promotion) = self.get_promotion() {
+            write!(f, "={}", UNICODE_PIECE[0][promotion as usize])?;
         }
         Ok(())
     }
@@ -90,6 +127,7 @@ impl PartialEq for Move {
 }
 
 impl Move {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         source_square: Square,
         target_square: Square,
@@ -115,6 +153,14 @@ impl Move {
         )
     }
 
+    pub fn from_value(value: u32) -> Self {
+        Self(value)
+    }
+
+    pub fn get_value(&self) -> u32 {
+        self.0
+    }
+
     pub fn get_source_square(&self) -> Square {
         self.0 & SOURCE_SQUARE_MASK
     }
@@ -129,10 +175,7 @@ impl Move {
     }
 
     pub fn get_promotion(&self) -> Option<Promotion> {
-        match Promotion::try_from((self.0 & PROMOTION_MASK) >> PROMOTION_SHIFT) {
-            Ok(promotion) => Some(promotion),
-            Err(_) => None,
-        }
+        Promotion::try_from((self.0 & PROMOTION_MASK) >> PROMOTION_SHIFT).ok()
     }
 
     pub fn get_capture(&self) -> bool {
@@ -164,10 +207,10 @@ mod tests {
         assert_eq!(mv.get_target_square(), F4);
         assert_eq!(mv.get_piece(), Piece::Pawn);
         assert_eq!(mv.get_promotion(), None);
-        assert_eq!(mv.get_capture(), true);
-        assert_eq!(mv.get_double_push(), false);
-        assert_eq!(mv.get_en_passant(), false);
-        assert_eq!(mv.get_castling(), false);
+        assert!(mv.get_capture());
+        assert!(!mv.get_double_push());
+        assert!(!mv.get_en_passant());
+        assert!(!mv.get_castling());
         let mv = Move::new(
             G7,
             G8,
@@ -182,9 +225,28 @@ mod tests {
         assert_eq!(mv.get_target_square(), G8);
         assert_eq!(mv.get_piece(), Piece::Pawn);
         assert_eq!(mv.get_promotion(), Some(Promotion::Queen));
-        assert_eq!(mv.get_capture(), false);
-        assert_eq!(mv.get_double_push(), false);
-        assert_eq!(mv.get_en_passant(), false);
-        assert_eq!(mv.get_castling(), false);
+        assert!(!mv.get_capture());
+        assert!(!mv.get_double_push());
+        assert!(!mv.get_en_passant());
+        assert!(!mv.get_castling());
+    }
+    #[test]
+    fn scored_move_list_tests() {
+        let e4 = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+        let nf3 = Move::new(G1, F3, Piece::Knight, None, false, false, false, false);
+        let d4 = Move::new(D2, D4, Piece::Pawn, None, false, true, false, false);
+
+        let mut moves = ScoredMoveList::new();
+        moves.add_move(e4, 10);
+        moves.add_move(nf3, 30);
+        moves.add_move(d4, 20);
+        assert_eq!(moves.len(), 3);
+
+        let picked: Vec<Move> = (0..moves.len())
+            .filter_map(|index| moves.pick_best(index))
+            .map(|scored| scored.mv)
+            .collect();
+        assert_eq!(picked, vec![nf3, d4, e4]);
+        assert_eq!(moves.pick_best(3), None);
     }
 }
@@ -1,5 +1,6 @@
-use crate::{board::Board, move_generator::movelist::Move};
+use crate::{bitboard::Bitboard, board::Board, move_generator::movelist::Move};
 
+#[derive(Default)]
 pub struct Perft {
     board: Board,
     pub nodes: u64,
@@ -7,20 +8,14 @@ pub struct Perft {
     pub en_passants: u64,
     pub castles: u64,
     pub promotions: u64,
+    pub checks: u64,
+    // single checks given by a piece other than the one moved, double checks are counted apart
+    pub discovered_checks: u64,
+    pub double_checks: u64,
+    pub checkmates: u64,
 }
 
 impl Perft {
-    pub fn default() -> Self {
-        Self {
-            board: Board::default(),
-            nodes: 0,
-            captures: 0,
-            en_passants: 0,
-            castles: 0,
-            promotions: 0,
-        }
-    }
-
     pub fn new(board: Board) -> Self {
         Self {
             board,
@@ -29,6 +24,10 @@ impl Perft {
             en_passants: 0,
             castles: 0,
             promotions: 0,
+            checks: 0,
+            discovered_checks: 0,
+            double_checks: 0,
+            checkmates: 0,
         }
     }
 
@@ -39,7 +38,13 @@ impl Perft {
             return;
         }
 
-        let moves = self.board.generate_moves();
+        let moves = self.board.generate_legal_moves();
+
+        // every generated move is legal, the last ply only needs to be counted
+        if depth == 1 {
+            self.nodes += moves.len() as u64;
+            return;
+        }
 
         for mv in moves.into_iter() {
             match self.board.make_move(mv, false) {
@@ -54,6 +59,24 @@ impl Perft {
         }
     }
 
+    // node count below each legal root move, printed as `e2e4: 20` to compare with other engines
+    pub fn divide(&mut self, depth: u64) -> Vec<(Move, u64)> {
+        let mut divide = vec![];
+        if depth == 0 {
+            return divide;
+        }
+
+        for mv in self.board.generate_legal_moves().into_iter() {
+            if self.board.make_move(mv, false).is_ok() {
+                let nodes = self.nodes;
+                self.run(depth - 1);
+                self.board.take_back_move();
+                divide.push((mv, self.nodes - nodes));
+            }
+        }
+        divide
+    }
+
     pub fn detailed_run(&mut self, depth: u64, leaf_node: Option<Move>) {
         if depth == 0 {
             // end of iteration reach
@@ -63,10 +86,11 @@ impl Perft {
             self.en_passants += mv.get_en_passant() as u64;
             self.castles += mv.get_castling() as u64;
             self.promotions += mv.get_promotion().is_some() as u64;
+            self.count_checks(mv);
             return;
         }
 
-        let moves = self.board.generate_moves();
+        let moves = self.board.generate_legal_moves();
 
         for mv in moves.into_iter() {
             match self.board.make_move(mv, false) {
@@ -80,6 +104,30 @@ impl Perft {
             }
         }
     }
+
+    // the board holds the position right after `mv`
+    fn count_checks(&mut self, mv: Move) {
+        let checkers = self.board.get_checkers();
+        if checkers == 0 {
+            return;
+        }
+
+        // when castling the rook is the piece moved next to the king
+        let mut moved = Bitboard::from_square(mv.get_target_square());
+        if mv.get_castling() {
+            match mv.get_target_square() % 8 {
+                6 => moved.set_square(mv.get_target_square() - 1),
+                _ => moved.set_square(mv.get_target_square() + 1),
+            }
+        }
+
+        self.checks += 1;
+        match checkers.count_occupied_squares() {
+            1 => self.discovered_checks += (checkers & !moved != 0) as u64,
+            _ => self.double_checks += 1,
+        }
+        self.checkmates += !self.board.has_legal_move() as u64;
+    }
 }
 
 #[cfg(test)]
@@ -96,7 +144,26 @@ mod tests {
         assert_eq!(perft.en_passants, 0);
         assert_eq!(perft.castles, 0);
         assert_eq!(perft.promotions, 0);
+        assert_eq!(perft.checks, 469);
+        assert_eq!(perft.discovered_checks, 0);
+        assert_eq!(perft.double_checks, 0);
+        assert_eq!(perft.checkmates, 8);
+    }
+    #[test]
+    fn divide_perft_test() {
+        let board = Board::default();
+        let mut perft = Perft::new(board);
+        let divide: Vec<String> = perft
+            .divide(3)
+            .iter()
+            .map(|(mv, nodes)| format!("{}: {}", mv.to_uci_string(), nodes))
+            .collect();
+        assert_eq!(divide.len(), 20);
+        assert!(divide.contains(&String::from("e2e4: 600")));
+        assert!(divide.contains(&String::from("g1f3: 440")));
+        assert_eq!(perft.nodes, 8_902);
     }
+
     #[test]
     fn position_2_perft_test() {
         let board =
@@ -109,6 +176,10 @@ mod tests {
         assert_eq!(perft.en_passants, 45);
         assert_eq!(perft.castles, 3162);
         assert_eq!(perft.promotions, 0);
+        assert_eq!(perft.checks, 993);
+        assert_eq!(perft.discovered_checks, 0);
+        assert_eq!(perft.double_checks, 0);
+        assert_eq!(perft.checkmates, 1);
     }
 
     #[test]
@@ -121,6 +192,10 @@ mod tests {
         assert_eq!(perft.en_passants, 1165);
         assert_eq!(perft.castles, 0);
         assert_eq!(perft.promotions, 0);
+        assert_eq!(perft.checks, 52_950);
+        assert_eq!(perft.discovered_checks, 1292);
+        assert_eq!(perft.double_checks, 3);
+        assert_eq!(perft.checkmates, 0);
     }
 
     #[test]
@@ -135,6 +210,10 @@ mod tests {
         assert_eq!(perft.en_passants, 0);
         assert_eq!(perft.castles, 7795);
         assert_eq!(perft.promotions, 60_032);
+        assert_eq!(perft.checks, 15_492);
+        assert_eq!(perft.discovered_checks, 19);
+        assert_eq!(perft.double_checks, 0);
+        assert_eq!(perft.checkmates, 5);
     }
 
     #[test]
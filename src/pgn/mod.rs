@@ -0,0 +1,91 @@
+mod reader;
+mod writer;
+
+use std::io;
+
+use crate::{
+    board::{ParseFenError, ParseSanError},
+    game::Game,
+};
+
+pub use reader::PgnReader;
+
+// written first and in this order, even when missing
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    // game still in progress, abandoned or unknown
+    Unknown,
+}
+
+impl GameResult {
+    pub fn from_pgn_str(result: &str) -> Option<Self> {
+        match result {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn to_pgn_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PgnError {
+    Io(io::ErrorKind),
+    BadTagFormat,
+    BadFen(ParseFenError),
+    // the san as written in the file
+    BadMove(String, ParseSanError),
+    // a variation opened before any move or a parenthesis left unbalanced
+    BadVariation,
+    UnterminatedComment,
+}
+
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    // in file order
+    pub tags: Vec<(String, String)>,
+    pub game: Game,
+    pub result: GameResult,
+}
+
+impl Default for PgnGame {
+    fn default() -> Self {
+        Self {
+            tags: vec![],
+            game: Game::default(),
+            result: GameResult::Unknown,
+        }
+    }
+}
+
+impl PgnGame {
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, current)) => *current = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::{
+    io::{BufRead, Lines},
+    time::Duration,
+};
+
+use crate::{
+    board::{Board, ParseSanError},
+    game::{Game, ROOT},
+};
+
+use super::{GameResult, PgnError, PgnGame};
+
+// suffix annotations and the glyph they stand for
+const SUFFIX_NAGS: [(&str, u8); 6] = [
+    ("!", 1),
+    ("?", 2),
+    ("!!", 3),
+    ("??", 4),
+    ("!?", 5),
+    ("?!", 6),
+];
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Tag(String, String),
+    // move numbers, moves and results
+    Symbol(String),
+    Comment(String),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+}
+
+// reads the games of a pgn file one at a time, a game is only held in memory while it is parsed
+pub struct PgnReader<R: BufRead> {
+    lines: Lines<R>,
+    // first tag of the next game, read while looking for the end of the current one
+    pending: Option<String>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            lines: input.lines(),
+            pending: None,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        let mut has_movetext = false;
+        let mut in_comment = false;
+
+        if let Some(line) = self.pending.take() {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        for line in &mut self.lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(PgnError::Io(err.kind()))),
+            };
+            let trimmed = line.trim_start();
+
+            if !in_comment {
+                // a tag after the movetext starts the next game
+                if trimmed.starts_with('[') && has_movetext {
+                    self.pending = Some(line);
+                    break;
+                }
+                // escape mechanism, the whole line is ignored
+                if line.starts_with('%') {
+                    continue;
+                }
+                if !trimmed.is_empty() && !trimmed.starts_with('[') {
+                    has_movetext = true;
+                }
+            }
+
+            for c in line.chars() {
+                match (in_comment, c) {
+                    (true, '}') => in_comment = false,
+                    (false, '{') => in_comment = true,
+                    (false, ';') => break,
+                    _ => {}
+                }
+            }
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        match text.trim().is_empty() {
+            true => None,
+            false => Some(PgnGame::from_pgn_str(&text)),
+        }
+    }
+}
+
+impl PgnGame {
+    // a single game, the moves are replayed and checked on the board
+    pub fn from_pgn_str(pgn: &str) -> Result<Self, PgnError> {
+        let mut tokens = tokenize(pgn)?.into_iter().peekable();
+
+        let mut tags = vec![];
+        while let Some(Token::Tag(_, _)) = tokens.peek() {
+            if let Some(Token::Tag(name, value)) = tokens.next() {
+                tags.push((name, value));
+            }
+        }
+
+        let mut board = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => Board::from_fen(fen).map_err(PgnError::BadFen)?,
+            None => Board::default(),
+        };
+        let mut game = Game::new(board.clone());
+        let mut node = ROOT;
+        // position and node to return to once a variation is over
+        let mut stack: Vec<(Board, usize)> = vec![];
+        let mut result = None;
+
+        for token in tokens {
+            match token {
+                Token::Tag(_, _) => return Err(PgnError::BadTagFormat),
+                Token::Comment(comment) => {
+                    let node = game.get_node_mut(node);
+                    match split_clock(&comment) {
+                        Some((clock, rest)) => {
+                            node.clock = Some(clock);
+                            if !rest.is_empty() {
+                                node.comments.push(rest);
+                            }
+                        }
+                        None => node.comments.push(comment),
+                    }
+                }
+                Token::Nag(nag) => game.get_node_mut(node).nags.push(nag),
+                // a variation replaces the last move played
+                Token::VariationStart => {
+                    let parent = game
+                        .get_node(node)
+                        .get_parent()
+                        .ok_or(PgnError::BadVariation)?;
+                    stack.push((board.clone(), node));
+                    board.take_back_move();
+                    node = parent;
+                }
+                Token::VariationEnd => {
+                    let (previous_board, previous_node) =
+                        stack.pop().ok_or(PgnError::BadVariation)?;
+                    board = previous_board;
+                    node = previous_node;
+                }
+                Token::Symbol(symbol) => {
+                    if let Some(game_result) = GameResult::from_pgn_str(&symbol) {
+                        if stack.is_empty() {
+                            result = Some(game_result);
+                        }
+                        continue;
+                    }
+
+                    let (san, nag) = split_symbol(&symbol);
+                    if san.is_empty() {
+                        continue;
+                    }
+                    let mv = board
+                        .parse_san(san)
+                        .map_err(|err| PgnError::BadMove(symbol.clone(), err))?;
+                    let illegal = |_| PgnError::BadMove(symbol.clone(), ParseSanError::IllegalMove);
+                    board.make_move(mv, false).map_err(illegal)?;
+                    node = game.add_move(node, mv).map_err(illegal)?;
+                    game.get_node_mut(node).nags.extend(nag);
+                }
+            }
+        }
+
+        if !stack.is_empty() {
+            return Err(PgnError::BadVariation);
+        }
+
+        // the termination marker wins over the tag
+        let result = result
+            .or_else(|| {
+                tags.iter()
+                    .find(|(name, _)| name == "Result")
+                    .and_then(|(_, value)| GameResult::from_pgn_str(value))
+            })
+            .unwrap_or(GameResult::Unknown);
+
+        Ok(Self { tags, game, result })
+    }
+}
+
+// drops the move number in front of a move and splits off its suffix annotation
+fn split_symbol(symbol: &str) -> (&str, Option<u8>) {
+    let san = match symbol.starts_with("0-0") {
+        true => symbol,
+        false => symbol.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.'),
+    };
+    let move_end = san.trim_end_matches(['!', '?']).len();
+    let nag = SUFFIX_NAGS
+        .iter()
+        .find(|(suffix, _)| *suffix == &san[move_end..])
+        .map(|(_, nag)| *nag);
+    (&san[..move_end], nag)
+}
+
+// takes a `[%clk 1:02:03.4]` command out of a comment
+fn split_clock(comment: &str) -> Option<(Duration, String)> {
+    let start = comment.find("[%clk ")?;
+    let end = start + comment[start..].find(']')?;
+
+    let mut fields = comment[start + 6..end].trim().split(':');
+    let hours = fields.next()?.parse::<u64>().ok()?;
+    let minutes = fields
+        .next()?
+        .parse::<u64>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+    // f64 parsing also accepts nan and inf
+    let seconds = fields
+        .next()?
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| (0.0..60.0).contains(seconds))?;
+    if fields.next().is_some() {
+        return None;
+    }
+    let clock = Duration::from_secs(
+        hours
+            .checked_mul(60)?
+            .checked_add(minutes)?
+            .checked_mul(60)?,
+    )
+    .checked_add(Duration::from_millis((seconds * 1000.0).round() as u64))?;
+
+    let rest = format!("{} {}", &comment[..start], &comment[end + 1..]);
+    Some((clock, rest.trim().to_string()))
+}
+
+fn tokenize(pgn: &str) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = vec![];
+    let mut chars = pgn.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '[' => {
+                let tag: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                let name = tag.trim();
+                if name.is_empty() || name.contains(char::is_whitespace) {
+                    return Err(PgnError::BadTagFormat);
+                }
+
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => value.extend(chars.next()),
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err(PgnError::BadTagFormat),
+                    }
+                }
+                let rest: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                if !rest.trim().is_empty() {
+                    return Err(PgnError::BadTagFormat);
+                }
+                tokens.push(Token::Tag(name.to_string(), value));
+            }
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => return Err(PgnError::UnterminatedComment),
+                    }
+                }
+                // line breaks inside a comment are only there to keep the lines short
+                let words: Vec<&str> = comment.split_whitespace().collect();
+                tokens.push(Token::Comment(words.join(" ")));
+            }
+            ';' => {
+                let comment: String = chars.by_ref().take_while(|c| *c != '\n').collect();
+                tokens.push(Token::Comment(comment.trim().to_string()));
+            }
+            '(' => tokens.push(Token::VariationStart),
+            ')' => tokens.push(Token::VariationEnd),
+            '$' => {
+                let mut nag = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    nag.push(c);
+                }
+                // unknown glyphs are dropped
+                if let Ok(nag) = nag.parse::<u8>() {
+                    tokens.push(Token::Nag(nag));
+                }
+            }
+            _ => {
+                let mut symbol = String::from(c);
+                while let Some(c) =
+                    chars.next_if(|c| !c.is_whitespace() && !"[]{}();$".contains(*c))
+                {
+                    symbol.push(c);
+                }
+                tokens.push(Token::Symbol(symbol));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::*, defs::Piece, move_generator::movelist::Move};
+
+    const GAMES: &str = r#"[Event "Casual game"]
+[Site "?"]
+[White "Anderssen, \"Adolf\""]
+[Annotator "Knightime"]
+
+{Played in a park} 1. e4 e5 2. Nf3 $1 (2. f4 exf4 (2... d5) 3. Bc4) 2... Nc6
+3. Bb5!? a6 ; the Morphy defence
+4. Ba4 { [%clk 0:04:59.5] } *
+[Event "Second game"]
+[SetUp "1"]
+[FEN "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1"]
+
+1.Ra8# 1-0
+
+% an escaped line
+[Event "Third game"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 Qh4#
+"#;
+
+    #[test]
+    fn pgn_reader_tests() {
+        let games: Vec<Result<PgnGame, PgnError>> = PgnReader::new(GAMES.as_bytes()).collect();
+        assert_eq!(games.len(), 3);
+
+        let first = games[0].as_ref().unwrap();
+        assert_eq!(first.get_tag("White"), Some("Anderssen, \"Adolf\""));
+        assert_eq!(first.get_tag("Annotator"), Some("Knightime"));
+        assert_eq!(first.result, GameResult::Unknown);
+
+        let game = &first.game;
+        assert_eq!(game.get_mainline().len(), 7);
+        assert_eq!(game.get_node(ROOT).comments, vec!["Played in a park"]);
+
+        let e4 = game.get_node(ROOT).get_children()[0];
+        let e5 = game.get_node(e4).get_children()[0];
+        let (nf3, f4) = match game.get_node(e5).get_children() {
+            [nf3, f4] => (*nf3, *f4),
+            children => panic!("unexpected children {:?}", children),
+        };
+        assert_eq!(game.get_node(nf3).nags, vec![1]);
+        #[rustfmt::skip]
+        assert_eq!(game.get_node(f4).get_move(), Some(Move::new(F2, F4, Piece::Pawn, None, false, true, false, false)));
+        // the nested variation replaces 2... exf4
+        let exf4 = game.get_node(f4).get_children()[0];
+        assert_eq!(game.get_node(f4).get_children().len(), 2);
+        assert_eq!(game.get_node(exf4).get_children().len(), 1);
+
+        let a6 = game
+            .get_node(
+                game.get_node(game.get_node(nf3).get_children()[0])
+                    .get_children()[0],
+            )
+            .get_children()[0];
+        assert_eq!(
+            game.get_node(game.get_node(a6).get_parent().unwrap()).nags,
+            vec![5]
+        );
+        assert_eq!(game.get_node(a6).comments, vec!["the Morphy defence"]);
+        let ba4 = game.get_node(a6).get_children()[0];
+        assert_eq!(
+            game.get_node(ba4).clock,
+            Some(Duration::from_millis(299_500))
+        );
+        assert!(game.get_node(ba4).comments.is_empty());
+
+        let second = games[1].as_ref().unwrap();
+        assert_eq!(second.result, GameResult::WhiteWins);
+        assert!(second
+            .game
+            .get_board(second.game.get_node(ROOT).get_children()[0])
+            .is_checkmate());
+
+        let third = games[2].as_ref().unwrap();
+        assert_eq!(third.result, GameResult::BlackWins);
+        assert_eq!(third.game.get_mainline().len(), 4);
+    }
+
+    #[test]
+    fn split_clock_tests() {
+        assert_eq!(
+            split_clock("[%clk 1:02:03.4] left"),
+            Some((Duration::from_millis(3_723_400), String::from("left")))
+        );
+        // malformed clocks are left in the comment
+        assert_eq!(split_clock("[%clk 99999999999999999:0:0]"), None);
+        assert_eq!(split_clock("[%clk 0:60:00]"), None);
+        assert_eq!(split_clock("[%clk 0:00:NaN]"), None);
+        assert_eq!(split_clock("[%clk 0:00:inf]"), None);
+        assert_eq!(split_clock("[%clk 0:00:60]"), None);
+        assert_eq!(split_clock("[%clk 0:00:-1]"), None);
+
+        let game = PgnGame::from_pgn_str("1. e4 {[%clk 0:00:nan]}").unwrap();
+        let e4 = game.game.get_node(ROOT).get_children()[0];
+        assert_eq!(game.game.get_node(e4).clock, None);
+        assert_eq!(game.game.get_node(e4).comments, vec!["[%clk 0:00:nan]"]);
+    }
+
+    #[test]
+    fn pgn_reader_errors_tests() {
+        assert_eq!(
+            PgnGame::from_pgn_str("1. e4 e5 2. Ke3").unwrap_err(),
+            PgnError::BadMove(String::from("Ke3"), ParseSanError::IllegalMove)
+        );
+        assert_eq!(
+            PgnGame::from_pgn_str("1. e4 (1. d4 d5 2. c4").unwrap_err(),
+            PgnError::BadVariation
+        );
+        assert_eq!(
+            PgnGame::from_pgn_str("(1. d4) 1. e4").unwrap_err(),
+            PgnError::BadVariation
+        );
+        assert_eq!(
+            PgnGame::from_pgn_str("1. e4 {unfinished").unwrap_err(),
+            PgnError::UnterminatedComment
+        );
+        assert_eq!(
+            PgnGame::from_pgn_str("[White Anderssen]\n1. e4").unwrap_err(),
+            PgnError::BadTagFormat
+        );
+        assert!(matches!(
+            PgnGame::from_pgn_str("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]").unwrap_err(),
+            PgnError::BadFen(_)
+        ));
+    }
+}
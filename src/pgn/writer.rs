@@ -0,0 +1,240 @@
+use crate::{
+    board::Board,
+    defs::Side,
+    game::{GameNode, NodeId, ROOT},
+};
+
+use super::{PgnGame, SEVEN_TAG_ROSTER};
+
+// export format line length
+const MAX_LINE_LENGTH: usize = 80;
+
+impl PgnGame {
+    pub fn to_pgn_string(&self) -> String {
+        let mut pgn = String::new();
+
+        for name in SEVEN_TAG_ROSTER.iter() {
+            let value = match *name {
+                "Result" => self.result.to_pgn_str(),
+                "Date" => self.get_tag(name).unwrap_or("????.??.??"),
+                _ => self.get_tag(name).unwrap_or("?"),
+            };
+            push_tag(&mut pgn, name, value);
+        }
+        for (name, value) in self.tags.iter() {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+                push_tag(&mut pgn, name, value);
+            }
+        }
+        let start = self.game.get_start();
+        // the history of the starting board does not matter, only its position
+        if self.get_tag("FEN").is_none() && start.to_fen() != Board::default().to_fen() {
+            push_tag(&mut pgn, "SetUp", "1");
+            push_tag(&mut pgn, "FEN", &start.to_fen());
+        }
+        pgn.push('\n');
+
+        let mut tokens = get_comment_tokens(self.game.get_node(ROOT));
+        self.write_line(ROOT, start.clone(), true, &mut tokens);
+        tokens.push(self.result.to_pgn_str().to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+            // a `;` comment runs to the end of the line
+            if token.starts_with(';') {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+        }
+        if !line.is_empty() {
+            pgn.push_str(&line);
+            pgn.push('\n');
+        }
+        pgn
+    }
+
+    // writes the continuation of `id`, whose move has already been written
+    fn write_line(
+        &self,
+        mut id: NodeId,
+        mut board: Board,
+        mut force_number: bool,
+        tokens: &mut Vec<String>,
+    ) {
+        while let Some((&main, variations)) = self.game.get_node(id).get_children().split_first() {
+            self.write_move(main, &board, force_number, tokens);
+
+            for &variation in variations {
+                let mut variation_tokens = vec![];
+                self.write_move(variation, &board, true, &mut variation_tokens);
+                let mut variation_board = board.clone();
+                if let Some(mv) = self.game.get_node(variation).get_move() {
+                    variation_board
+                        .make_move(mv, false)
+                        .expect("moves are checked when they enter the tree");
+                }
+                let has_comments = !get_comment_tokens(self.game.get_node(variation)).is_empty();
+                self.write_line(
+                    variation,
+                    variation_board,
+                    has_comments,
+                    &mut variation_tokens,
+                );
+
+                variation_tokens[0].insert(0, '(');
+                match variation_tokens.last_mut() {
+                    Some(last) if !last.starts_with(';') => last.push(')'),
+                    _ => variation_tokens.push(String::from(")")),
+                }
+                tokens.extend(variation_tokens);
+            }
+
+            // black moves need their number again after an interruption
+            force_number =
+                !variations.is_empty() || !get_comment_tokens(self.game.get_node(main)).is_empty();
+            if let Some(mv) = self.game.get_node(main).get_move() {
+                board
+                    .make_move(mv, false)
+                    .expect("moves are checked when they enter the tree");
+            }
+            id = main;
+        }
+    }
+
+    fn write_move(&self, id: NodeId, board: &Board, force_number: bool, tokens: &mut Vec<String>) {
+        let node = self.game.get_node(id);
+        let mv = match node.get_move() {
+            Some(mv) => mv,
+            None => return,
+        };
+
+        let state = board.get_state();
+        match state.side_to_move {
+            Side::White => tokens.push(format!("{}.", state.fullmove_number)),
+            Side::Black if force_number => tokens.push(format!("{}...", state.fullmove_number)),
+            Side::Black => {}
+        }
+        tokens.push(board.move_to_san(mv));
+        tokens.extend(node.nags.iter().map(|nag| format!("${}", nag)));
+        tokens.extend(get_comment_tokens(node));
+    }
+}
+
+// the clock is written as a `[%clk 0:04:59.5]` command in a comment of its own, the
+// comments are split into words so that long ones can be wrapped
+fn get_comment_tokens(node: &GameNode) -> Vec<String> {
+    let mut tokens = vec![];
+    if let Some(clock) = node.clock {
+        let seconds = clock.as_secs();
+        let mut command = format!(
+            "{{[%clk {}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        if clock.subsec_millis() >= 100 {
+            command.push_str(&format!(".{}", clock.subsec_millis() / 100));
+        }
+        command.push_str("]}");
+        tokens.push(command);
+    }
+    for comment in node.comments.iter() {
+        let mut words: Vec<String> = comment.split_whitespace().map(String::from).collect();
+        // a brace would end the comment early, such a comment runs to the end of the line
+        if comment.contains('}') {
+            tokens.push(format!("; {}", words.join(" ")));
+            continue;
+        }
+        match words.first_mut() {
+            Some(first) => first.insert(0, '{'),
+            None => words.push(String::from("{")),
+        }
+        if let Some(last) = words.last_mut() {
+            last.push('}');
+        }
+        tokens.extend(words);
+    }
+    tokens
+}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_pgn_string_tests() {
+        let pgn = "[Event \"Casual game\"]
+[Site \"?\"]
+[Date \"????.??.??\"]
+[Round \"?\"]
+[White \"Anderssen, \\\"Adolf\\\"\"]
+[Black \"?\"]
+[Result \"1-0\"]
+[Annotator \"Knightime\"]
+
+{Played in a park} 1. e4 e5 2. Nf3 $1 (2. f4 exf4 (2... d5) 3. Bc4) 2... Nc6 3.
+Bb5 $5 a6 {the Morphy defence} 4. Ba4 {[%clk 0:04:59.5]} {Clock: 4:59} 4... Nf6
+1-0
+";
+        let game = PgnGame::from_pgn_str(pgn).unwrap();
+        assert_eq!(game.to_pgn_string(), pgn);
+
+        let mut game = PgnGame::from_pgn_str("1. d4 d5 2. c4").unwrap();
+        game.set_tag("Event", "Training");
+        assert!(game.to_pgn_string().starts_with("[Event \"Training\"]\n"));
+        assert!(game.to_pgn_string().ends_with("\n1. d4 d5 2. c4 *\n"));
+
+        let fen = "6k1/5ppp/8/8/8/8/8/R5K1 b - - 0 1";
+        let game = PgnGame::from_pgn_str(&format!("[FEN \"{}\"]\n1... h6", fen)).unwrap();
+        assert!(game
+            .to_pgn_string()
+            .contains(&format!("[FEN \"{}\"]\n", fen)));
+        assert!(game.to_pgn_string().ends_with("\n1... h6 *\n"));
+        let mut game = PgnGame {
+            game: game.game,
+            ..PgnGame::default()
+        };
+        game.tags.clear();
+        assert!(game.to_pgn_string().contains("[SetUp \"1\"]\n"));
+
+        let game = PgnGame::from_pgn_str("1. e4 (1. d4 ; {braces} inside\n) 1... e5").unwrap();
+        let pgn = game.to_pgn_string();
+        assert!(pgn.ends_with("\n1. e4 (1. d4 ; {braces} inside\n) 1... e5 *\n"));
+        let game = PgnGame::from_pgn_str(&pgn).unwrap();
+        let d4 = game.game.get_node(ROOT).get_children()[1];
+        assert_eq!(game.game.get_node(d4).comments, vec!["{braces} inside"]);
+        assert_eq!(game.game.get_mainline().len(), 2);
+    }
+
+    #[test]
+    fn long_comment_tests() {
+        let comment = "the bishop could also go to c4, where it eyes the f7 pawn, but after Nf6 \
+            black is quick to strike back in the centre with d5";
+        let game =
+            PgnGame::from_pgn_str(&format!("1. e4 e5 2. Nf3 Nc6 3. Bb5 {{{}}}", comment)).unwrap();
+        let pgn = game.to_pgn_string();
+        assert!(pgn.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+
+        let game = PgnGame::from_pgn_str(&pgn).unwrap();
+        let mut id = ROOT;
+        while let Some(&next) = game.game.get_node(id).get_children().first() {
+            id = next;
+        }
+        assert_eq!(game.game.get_node(id).comments, vec![comment]);
+    }
+}
@@ -0,0 +1,785 @@
+mod ordering;
+pub mod thread;
+pub mod time;
+pub mod tt;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{
+    board::Board,
+    eval::evaluate,
+    move_generator::movelist::{Move, MoveList},
+};
+
+use self::{
+    ordering::{is_quiet, History, Killers, MovePicker},
+    time::{TimeManager, DEFAULT_MOVE_OVERHEAD},
+    tt::{score_from_tt, score_to_tt, Bound, TranspositionTable},
+};
+
+pub const MAX_PLY: usize = 64;
+// every score fits the 16 bits a transposition table entry has for it
+pub const INFINITY: i32 = 32_000;
+pub const MATE_VALUE: i32 = 31_000;
+// any score above this bound is a forced mate
+pub const MATE_SCORE: i32 = MATE_VALUE - MAX_PLY as i32;
+// how often, in nodes, the clock and the stop signal are looked at during the search
+const TIME_CHECK_INTERVAL: u64 = 1024;
+// root moves are only reported once the search has been running for that long
+const CURRMOVE_DELAY: Duration = Duration::from_secs(1);
+
+// what a `go` command asks for, the search runs until the first limit is reached
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    // mate in that many moves
+    pub mate: Option<u32>,
+    pub movetime: Option<Duration>,
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+    pub movestogo: Option<u32>,
+    pub infinite: bool,
+    // the clock is ignored until the gui sends `ponderhit`
+    pub ponder: bool,
+    // only these root moves are searched when not empty
+    pub searchmoves: Vec<Move>,
+}
+
+impl SearchLimits {
+    pub fn get_max_depth(&self) -> u32 {
+        // a mate in n moves is seen once the reply to the nth move is searched
+        let mate_depth = self.mate.map(|mate| mate.saturating_mul(2));
+        [self.depth, mate_depth]
+            .iter()
+            .flatten()
+            .copied()
+            .min()
+            .unwrap_or(MAX_PLY as u32 - 1)
+            .clamp(1, MAX_PLY as u32 - 1)
+    }
+}
+
+// set from outside the search thread while it is running
+#[derive(Debug, Default)]
+pub struct SearchSignals {
+    pub stop: AtomicBool,
+    pub ponder: AtomicBool,
+}
+
+// progress reported while the search runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchInfo {
+    // sent for every line each time an iteration completes, lines are numbered from 1
+    Iteration {
+        depth: u32,
+        multipv: usize,
+        seldepth: u32,
+        score: i32,
+        nodes: u64,
+        time: Duration,
+        hashfull: usize,
+        pv: Vec<Move>,
+    },
+    // the root move being searched, numbered from 1
+    CurrentMove {
+        depth: u32,
+        mv: Move,
+        number: usize,
+    },
+}
+
+pub type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+    pub pv: Vec<Move>,
+    // best line first, the first one is the pv above
+    pub lines: Vec<PvLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PvLine {
+    pub score: i32,
+    pub pv: Vec<Move>,
+}
+
+// the `multipv` best lines of the position, searched with a table of its own
+pub fn analyse(board: &Board, limits: SearchLimits, multipv: usize) -> SearchResult {
+    Search::new(board.clone(), Arc::default())
+        .with_multipv(multipv)
+        .run_with_limits(limits)
+}
+
+impl Board {
+    pub fn analyse(&self, limits: SearchLimits, multipv: usize) -> SearchResult {
+        analyse(self, limits, multipv)
+    }
+}
+
+pub struct Search {
+    board: Board,
+    tt: Arc<TranspositionTable>,
+    nodes: u64,
+    ply: usize,
+    seldepth: usize,
+    pv_table: [[Option<Move>; MAX_PLY]; MAX_PLY],
+    pv_length: [usize; MAX_PLY],
+    killers: Killers,
+    history: Box<History>,
+    limits: SearchLimits,
+    time: TimeManager,
+    move_overhead: Duration,
+    multipv: usize,
+    // root moves already given a line in the current iteration
+    excluded_root_moves: Vec<Move>,
+    threads: usize,
+    start_depth: u32,
+    // nodes of every thread taking part in the search, each one adds its own regularly
+    node_counter: Arc<AtomicU64>,
+    published_nodes: u64,
+    completed_depth: u32,
+    stopped: bool,
+    signals: Arc<SearchSignals>,
+    info_callback: Option<InfoCallback>,
+}
+
+impl Search {
+    pub fn new(board: Board, tt: Arc<TranspositionTable>) -> Self {
+        let side = board.get_state().side_to_move;
+        Self {
+            board,
+            tt,
+            nodes: 0,
+            ply: 0,
+            seldepth: 0,
+            pv_table: [[None; MAX_PLY]; MAX_PLY],
+            pv_length: [0; MAX_PLY],
+            killers: Killers::default(),
+            history: Box::default(),
+            limits: SearchLimits::default(),
+            time: TimeManager::new(&SearchLimits::default(), side, DEFAULT_MOVE_OVERHEAD),
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            multipv: 1,
+            excluded_root_moves: vec![],
+            threads: 1,
+            start_depth: 1,
+            node_counter: Arc::default(),
+            published_nodes: 0,
+            completed_depth: 0,
+            stopped: false,
+            signals: Arc::default(),
+            info_callback: None,
+        }
+    }
+
+    pub fn with_move_overhead(mut self, move_overhead: Duration) -> Self {
+        self.move_overhead = move_overhead;
+        self
+    }
+
+    pub fn with_multipv(mut self, multipv: usize) -> Self {
+        self.multipv = multipv.max(1);
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn with_info_callback(mut self, info_callback: InfoCallback) -> Self {
+        self.info_callback = Some(info_callback);
+        self
+    }
+
+    pub fn with_signals(mut self, signals: Arc<SearchSignals>) -> Self {
+        self.signals = signals;
+        self
+    }
+
+    pub fn run(&mut self, depth: u32) -> SearchResult {
+        self.run_with_limits(SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        })
+    }
+
+    // lazy smp : the helper threads search the same position on their own and only share the
+    // transposition table, the main thread alone decides when to stop and what to play
+    pub fn run_with_limits(&mut self, limits: SearchLimits) -> SearchResult {
+        self.time = TimeManager::new(
+            &limits,
+            self.board.get_state().side_to_move,
+            self.move_overhead,
+        );
+        self.limits = limits;
+        self.tt.new_search();
+
+        let helper_signals = Arc::new(SearchSignals::default());
+        let helpers: Vec<JoinHandle<()>> = (1..self.threads)
+            .map(|index| self.spawn_helper(index, &helper_signals))
+            .collect();
+
+        let result = self.iterate();
+
+        helper_signals.stop.store(true, Ordering::Relaxed);
+        for helper in helpers {
+            // a panicking helper only takes its share of the table with it
+            let _ = helper.join();
+        }
+        result
+    }
+
+    fn spawn_helper(&self, index: usize, signals: &Arc<SearchSignals>) -> JoinHandle<()> {
+        let mut helper =
+            Search::new(self.board.clone(), Arc::clone(&self.tt)).with_signals(Arc::clone(signals));
+        helper.node_counter = Arc::clone(&self.node_counter);
+        helper.limits = SearchLimits {
+            depth: Some(self.limits.get_max_depth()),
+            infinite: true,
+            searchmoves: self.limits.searchmoves.clone(),
+            ..SearchLimits::default()
+        };
+        // half of the helpers start one ply deeper so that the threads spread over the tree
+        helper.start_depth = 1 + (index % 2) as u32;
+
+        std::thread::spawn(move || {
+            helper.iterate();
+        })
+    }
+
+    // iterative deepening until a limit is reached, the iteration interrupted by the clock or
+    // the node count is thrown away. with multipv each iteration searches the root again for
+    // every line, leaving out the moves of the lines already found
+    fn iterate(&mut self) -> SearchResult {
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            pv: vec![],
+            lines: vec![],
+        };
+        self.completed_depth = 0;
+        self.stopped = false;
+
+        let max_depth = self.limits.get_max_depth();
+        for current_depth in self.start_depth.min(max_depth)..=max_depth {
+            self.seldepth = 0;
+            self.excluded_root_moves.clear();
+            let mut lines: Vec<PvLine> = vec![];
+
+            while lines.len() < self.multipv {
+                let score = self.negamax(-INFINITY, INFINITY, current_depth);
+                if self.stopped {
+                    break;
+                }
+                let pv = self.get_pv();
+                match pv.first() {
+                    Some(mv) => self.excluded_root_moves.push(*mv),
+                    // the root has no legal move, or no move left for another line
+                    None if lines.is_empty() => {
+                        lines.push(PvLine { score, pv });
+                        break;
+                    }
+                    None => break,
+                }
+                lines.push(PvLine { score, pv });
+            }
+            if self.stopped {
+                break;
+            }
+            self.completed_depth = current_depth;
+            lines.sort_by_key(|line| -line.score);
+
+            for (index, line) in lines.iter().enumerate() {
+                let info = SearchInfo::Iteration {
+                    depth: current_depth,
+                    multipv: index + 1,
+                    seldepth: self.seldepth as u32,
+                    score: line.score,
+                    nodes: self.get_total_nodes(),
+                    time: self.time.get_elapsed(),
+                    hashfull: self.tt.hashfull(),
+                    pv: line.pv.clone(),
+                };
+                self.report(info);
+            }
+
+            result = SearchResult {
+                best_move: lines[0].pv.first().copied(),
+                score: lines[0].score,
+                depth: current_depth,
+                nodes: self.get_total_nodes(),
+                pv: lines[0].pv.clone(),
+                lines,
+            };
+
+            // forced mates will not get any better by searching deeper
+            if result.best_move.is_none()
+                || result
+                    .lines
+                    .iter()
+                    .all(|line| line.score.abs() > MATE_SCORE)
+            {
+                break;
+            }
+            if !self.is_pondering() && self.time.should_stop_iterating() {
+                break;
+            }
+        }
+
+        self.publish_nodes();
+        result
+    }
+
+    fn report(&mut self, info: SearchInfo) {
+        if let Some(info_callback) = self.info_callback.as_mut() {
+            info_callback(&info);
+        }
+    }
+
+    fn get_pv(&self) -> Vec<Move> {
+        self.pv_table[0][..self.pv_length[0]]
+            .iter()
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    // the clock is ignored while pondering, after `ponderhit` the budget still counts from `go`
+    fn is_pondering(&self) -> bool {
+        self.signals.ponder.load(Ordering::Relaxed)
+    }
+
+    fn get_total_nodes(&self) -> u64 {
+        self.node_counter.load(Ordering::Relaxed) + self.nodes - self.published_nodes
+    }
+
+    fn publish_nodes(&mut self) {
+        self.node_counter
+            .fetch_add(self.nodes - self.published_nodes, Ordering::Relaxed);
+        self.published_nodes = self.nodes;
+    }
+
+    // limits are only enforced once the first iteration has found a move to play
+    fn check_limits(&mut self) -> bool {
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL) {
+            self.publish_nodes();
+        }
+        if !self.stopped && self.completed_depth > 0 {
+            let out_of_nodes = self
+                .limits
+                .nodes
+                .is_some_and(|nodes| self.get_total_nodes() >= nodes);
+            let interrupted = self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+                && (self.signals.stop.load(Ordering::Relaxed)
+                    || (!self.is_pondering() && self.time.is_out_of_time()));
+            self.stopped = out_of_nodes || interrupted;
+        }
+        self.stopped
+    }
+
+    fn negamax(&mut self, mut alpha: i32, beta: i32, depth: u32) -> i32 {
+        self.pv_length[self.ply] = self.ply;
+        self.seldepth = self.seldepth.max(self.ply);
+        if self.check_limits() {
+            return 0;
+        }
+
+        if depth == 0 {
+            return self.quiescence(alpha, beta);
+        }
+        if self.ply >= MAX_PLY - 1 {
+            self.nodes += 1;
+            return evaluate(self.board.get_state());
+        }
+
+        self.nodes += 1;
+
+        let hash = self.board.get_hash();
+        let tt_entry = self.tt.probe(hash);
+
+        // the root always searches its moves so that a best move and a pv are available
+        if let Some(entry) = tt_entry.filter(|entry| self.ply > 0 && entry.depth as u32 >= depth) {
+            let score = score_from_tt(entry.score, self.ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return beta,
+                Bound::Upper if score <= alpha => return alpha,
+                _ => {}
+            }
+        }
+
+        let mut best_move = None;
+        let mut bound = Bound::Upper;
+        // a root searched without its best moves must not replace the real root entry
+        let store = self.ply > 0 || self.excluded_root_moves.is_empty();
+
+        let mut moves = self.board.generate_legal_moves();
+        if moves.is_empty() {
+            return match self.board.is_check() {
+                true => -MATE_VALUE + self.ply as i32,
+                false => 0,
+            };
+        }
+        if self.ply == 0 {
+            moves = self.filter_root_moves(moves);
+            if moves.is_empty() {
+                return alpha;
+            }
+        }
+        let picker = MovePicker::new(
+            &self.board,
+            moves,
+            tt_entry.and_then(|entry| entry.best_move),
+            self.killers.get(self.ply),
+            &self.history,
+        );
+
+        let mut move_number = 0;
+        for mv in picker {
+            if self.board.make_move(mv, false).is_err() {
+                continue;
+            }
+            move_number += 1;
+            if self.ply == 0 && self.time.get_elapsed() >= CURRMOVE_DELAY {
+                self.report(SearchInfo::CurrentMove {
+                    depth,
+                    mv,
+                    number: move_number,
+                });
+            }
+
+            self.ply += 1;
+            let score = -self.negamax(-beta, -alpha, depth - 1);
+            self.ply -= 1;
+            self.board.take_back_move();
+
+            // the score of an interrupted subtree is meaningless
+            if self.stopped {
+                return 0;
+            }
+
+            // fail-hard beta cutoff
+            if score >= beta {
+                // quiet moves refuting a position are likely to refute its siblings too
+                if is_quiet(mv) {
+                    self.killers.store(self.ply, mv);
+                    self.history
+                        .update(self.board.get_state().side_to_move, mv, depth);
+                }
+                if store {
+                    self.tt.store(
+                        hash,
+                        Some(mv),
+                        score_to_tt(beta, self.ply),
+                        depth as u8,
+                        Bound::Lower,
+                    );
+                }
+                return beta;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best_move = Some(mv);
+                bound = Bound::Exact;
+                self.update_pv(mv);
+            }
+        }
+
+        if store {
+            self.tt.store(
+                hash,
+                best_move,
+                score_to_tt(alpha, self.ply),
+                depth as u8,
+                bound,
+            );
+        }
+
+        alpha
+    }
+
+    // only captures and promotions are searched past the horizon so the evaluation is never
+    // taken in the middle of an exchange
+    fn quiescence(&mut self, mut alpha: i32, beta: i32) -> i32 {
+        if self.check_limits() {
+            return 0;
+        }
+        self.nodes += 1;
+        self.seldepth = self.seldepth.max(self.ply);
+
+        // the side to move is assumed to have a quiet move at least as good as the evaluation
+        let stand_pat = evaluate(self.board.get_state());
+        if self.ply >= MAX_PLY - 1 || stand_pat >= beta {
+            return stand_pat.min(beta);
+        }
+        alpha = alpha.max(stand_pat);
+
+        let picker = MovePicker::new_captures(&self.board, self.board.generate_captures());
+
+        for mv in picker {
+            // captures losing material are pruned
+            if self.board.see(mv) < 0 || self.board.make_move(mv, false).is_err() {
+                continue;
+            }
+
+            self.ply += 1;
+            let score = -self.quiescence(-beta, -alpha);
+            self.ply -= 1;
+            self.board.take_back_move();
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    fn filter_root_moves(&self, moves: MoveList) -> MoveList {
+        let mut filtered = MoveList::new();
+        for mv in moves.into_iter() {
+            let searched =
+                self.limits.searchmoves.is_empty() || self.limits.searchmoves.contains(&mv);
+            if searched && !self.excluded_root_moves.contains(&mv) {
+                filtered.add_move(mv);
+            }
+        }
+        filtered
+    }
+
+    fn update_pv(&mut self, mv: Move) {
+        let ply = self.ply;
+        self.pv_table[ply][ply] = Some(mv);
+        for next_ply in (ply + 1)..self.pv_length[ply + 1] {
+            self.pv_table[ply][next_ply] = self.pv_table[ply + 1][next_ply];
+        }
+        self.pv_length[ply] = self.pv_length[ply + 1];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::*, defs::Piece};
+
+    #[test]
+    fn search_finds_mate_in_one_tests() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(3);
+        #[rustfmt::skip]
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(A1, A8, Piece::Rook, None, false, false, false, false))
+        );
+        assert_eq!(result.score, MATE_VALUE - 1);
+        assert_eq!(result.pv.len(), 1);
+        // the mate is only seen once the reply is searched, deeper iterations are skipped
+        assert_eq!(result.depth, 2);
+    }
+
+    #[test]
+    fn search_wins_material_tests() {
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(3);
+        #[rustfmt::skip]
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(D1, D5, Piece::Rook, None, true, false, false, false))
+        );
+        assert_eq!(result.depth, 3);
+        assert!(result.score > 0);
+        assert_eq!(result.pv.len(), 3);
+    }
+
+    #[test]
+    fn search_sees_past_the_horizon_tests() {
+        // the pawn on d5 is defended, taking it loses the queen on the next move
+        let board = Board::from_fen("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(1);
+        #[rustfmt::skip]
+        assert_ne!(
+            result.best_move,
+            Some(Move::new(D1, D5, Piece::Queen, None, true, false, false, false))
+        );
+        assert!(result.score > 0);
+    }
+
+    #[test]
+    fn search_only_returns_legal_moves_tests() {
+        // the king is boxed in a corner, capturing the rook is the only legal move
+        let board = Board::from_fen("k7/8/8/8/8/8/1r6/K7 w - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(2);
+        #[rustfmt::skip]
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(A1, B2, Piece::King, None, true, false, false, false))
+        );
+    }
+
+    #[test]
+    fn search_without_legal_moves_tests() {
+        // stalemate
+        let board = Board::from_fen("k7/8/1Q6/8/8/8/8/7K b - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(2);
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, 0);
+
+        // checkmate
+        let board = Board::from_fen("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).run(2);
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, -MATE_VALUE);
+    }
+
+    #[test]
+    fn search_limits_tests() {
+        let board = Board::default();
+
+        // the node budget interrupts the search once the first iteration is complete
+        let limits = SearchLimits {
+            nodes: Some(1000),
+            ..SearchLimits::default()
+        };
+        let result = Search::new(board.clone(), Arc::default()).run_with_limits(limits);
+        assert!(result.best_move.is_some());
+        assert!(result.depth < MAX_PLY as u32 - 1);
+
+        let limits = SearchLimits {
+            movetime: Some(Duration::from_millis(50)),
+            ..SearchLimits::default()
+        };
+        let result = Search::new(board.clone(), Arc::default()).run_with_limits(limits);
+        assert!(result.best_move.is_some());
+
+        #[rustfmt::skip]
+        let only_move = Move::new(A2, A3, Piece::Pawn, None, false, false, false, false);
+        let limits = SearchLimits {
+            depth: Some(2),
+            searchmoves: vec![only_move],
+            ..SearchLimits::default()
+        };
+        let result = Search::new(board, Arc::default()).run_with_limits(limits);
+        assert_eq!(result.best_move, Some(only_move));
+
+        let limits = SearchLimits {
+            mate: Some(2),
+            depth: Some(10),
+            ..SearchLimits::default()
+        };
+        assert_eq!(limits.get_max_depth(), 4);
+    }
+
+    #[test]
+    fn search_info_tests() {
+        let infos = Arc::new(std::sync::Mutex::new(vec![]));
+        let reported = Arc::clone(&infos);
+        let result = Search::new(Board::default(), Arc::default())
+            .with_info_callback(Box::new(move |info| {
+                reported.lock().unwrap().push(info.clone())
+            }))
+            .run(3);
+
+        let infos = infos.lock().unwrap();
+        assert_eq!(infos.len(), 3);
+        for (index, info) in infos.iter().enumerate() {
+            match info {
+                SearchInfo::Iteration {
+                    depth, seldepth, ..
+                } => {
+                    assert_eq!(*depth, index as u32 + 1);
+                    assert!(*seldepth >= *depth);
+                }
+                _ => panic!("unexpected {:?}", info),
+            }
+        }
+        match &infos[2] {
+            SearchInfo::Iteration {
+                score, nodes, pv, ..
+            } => {
+                assert_eq!(*score, result.score);
+                assert_eq!(*nodes, result.nodes);
+                assert_eq!(*pv, result.pv);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn multipv_tests() {
+        let limits = SearchLimits {
+            depth: Some(3),
+            ..SearchLimits::default()
+        };
+        let tt = Arc::new(TranspositionTable::new(1));
+        let result = Search::new(Board::default(), Arc::clone(&tt))
+            .with_multipv(3)
+            .run_with_limits(limits.clone());
+        assert_eq!(result.lines.len(), 3);
+        assert_eq!(result.pv, result.lines[0].pv);
+        assert_eq!(result.score, result.lines[0].score);
+        assert!(result
+            .lines
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        let first_moves: Vec<Move> = result.lines.iter().map(|line| line.pv[0]).collect();
+        assert!(!first_moves[1..].contains(&first_moves[0]));
+        assert_ne!(first_moves[1], first_moves[2]);
+        // the searches of the later lines leave the root entry alone
+        let root = tt.probe(Board::default().get_hash()).unwrap();
+        assert_eq!(root.best_move, result.best_move);
+        assert_eq!(root.bound, Bound::Exact);
+
+        // the checked king has two moves, there can not be more lines than that
+        let board = Board::from_fen("k7/8/8/8/8/8/8/K6r w - - 0 1").unwrap();
+        let result = board.analyse(limits, 5);
+        assert_eq!(result.lines.len(), 2);
+    }
+
+    #[test]
+    fn lazy_smp_tests() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let result = Search::new(board, Arc::default()).with_threads(4).run(3);
+        #[rustfmt::skip]
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(A1, A8, Piece::Rook, None, false, false, false, false))
+        );
+
+        // a single thread always searches the same tree
+        let limits = SearchLimits {
+            depth: Some(4),
+            ..SearchLimits::default()
+        };
+        let single = analyse(&Board::default(), limits.clone(), 1);
+        assert_eq!(analyse(&Board::default(), limits.clone(), 1), single);
+
+        // every helper completes at least one iteration, its nodes are added to the total
+        let mut search = Search::new(Board::default(), Arc::default()).with_threads(3);
+        let result = search.run_with_limits(limits);
+        assert_eq!(result.depth, 4);
+        assert!(search.get_total_nodes() > search.nodes);
+    }
+}
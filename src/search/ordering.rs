@@ -0,0 +1,220 @@
+use crate::{
+    board::Board,
+    defs::{Piece, Side},
+    move_generator::movelist::{Move, MoveList, ScoredMoveList},
+};
+
+use super::MAX_PLY;
+
+// piece ranks for capture ordering, indexed by Piece
+const MVV_LVA_RANKS: [i32; 6] = [6, 5, 1, 2, 3, 4];
+
+// the hash move comes first, then captures and promotions, then killers, then the other quiet
+// moves sorted by their history score, which stays below the killers
+const HASH_MOVE_SCORE: i32 = 1_000_000;
+const CAPTURE_SCORE: i32 = 100_000;
+const KILLER_SCORES: [i32; 2] = [90_000, 80_000];
+const HISTORY_MAX: i32 = 50_000;
+
+// most valuable victim first, the least valuable attacker breaking ties
+pub fn mvv_lva(board: &Board, mv: Move) -> i32 {
+    let victim = match board.get_piece_on(mv.get_target_square()) {
+        Some((_, piece)) => MVV_LVA_RANKS[piece as usize],
+        None if mv.get_en_passant() => MVV_LVA_RANKS[Piece::Pawn as usize],
+        None => 0,
+    };
+    let promotion = mv.get_promotion().map_or(0, |promotion| {
+        MVV_LVA_RANKS[Piece::from(promotion) as usize]
+    });
+
+    (victim + promotion) * 10 - MVV_LVA_RANKS[mv.get_piece() as usize]
+}
+
+pub fn is_quiet(mv: Move) -> bool {
+    !mv.get_capture() && mv.get_promotion().is_none()
+}
+
+// quiet moves which produced a beta cutoff, two per ply
+pub struct Killers([[Option<Move>; 2]; MAX_PLY]);
+
+impl Default for Killers {
+    fn default() -> Self {
+        Self([[None; 2]; MAX_PLY])
+    }
+}
+
+impl Killers {
+    pub fn get(&self, ply: usize) -> [Option<Move>; 2] {
+        self.0[ply]
+    }
+
+    pub fn store(&mut self, ply: usize, mv: Move) {
+        if self.0[ply][0] != Some(mv) {
+            self.0[ply][1] = self.0[ply][0];
+            self.0[ply][0] = Some(mv);
+        }
+    }
+}
+
+// butterfly table of quiet moves producing cutoffs, indexed by side, source and target square
+pub struct History([[[i32; 64]; 64]; 2]);
+
+impl Default for History {
+    fn default() -> Self {
+        Self([[[0; 64]; 64]; 2])
+    }
+}
+
+impl History {
+    pub fn get(&self, side: Side, mv: Move) -> i32 {
+        self.0[side as usize][mv.get_source_square() as usize][mv.get_target_square() as usize]
+    }
+
+    // deeper cutoffs are more reliable and get a bigger bonus
+    pub fn update(&mut self, side: Side, mv: Move, depth: u32) {
+        let entry = &mut self.0[side as usize][mv.get_source_square() as usize]
+            [mv.get_target_square() as usize];
+        *entry += (depth * depth) as i32;
+
+        // halving every entry keeps the table bounded while preserving the ordering
+        if *entry > HISTORY_MAX {
+            for value in self.0.iter_mut().flatten().flatten() {
+                *value /= 2;
+            }
+        }
+    }
+}
+
+// hands out the moves best first, scoring them once and sorting them lazily
+pub struct MovePicker {
+    moves: ScoredMoveList,
+    index: usize,
+}
+
+impl MovePicker {
+    pub fn new(
+        board: &Board,
+        moves: MoveList,
+        hash_move: Option<Move>,
+        killers: [Option<Move>; 2],
+        history: &History,
+    ) -> Self {
+        let side = board.get_state().side_to_move;
+        let mut scored_moves = ScoredMoveList::new();
+
+        for mv in moves.into_iter() {
+            let score = if Some(mv) == hash_move {
+                HASH_MOVE_SCORE
+            } else if !is_quiet(mv) {
+                CAPTURE_SCORE + mvv_lva(board, mv)
+            } else if Some(mv) == killers[0] {
+                KILLER_SCORES[0]
+            } else if Some(mv) == killers[1] {
+                KILLER_SCORES[1]
+            } else {
+                history.get(side, mv)
+            };
+            scored_moves.add_move(mv, score);
+        }
+
+        Self {
+            moves: scored_moves,
+            index: 0,
+        }
+    }
+
+    // captures only, as searched by the quiescence search
+    pub fn new_captures(board: &Board, moves: MoveList) -> Self {
+        let mut scored_moves = ScoredMoveList::new();
+        for mv in moves.into_iter() {
+            scored_moves.add_move(mv, mvv_lva(board, mv));
+        }
+
+        Self {
+            moves: scored_moves,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for MovePicker {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let scored_move = self.moves.pick_best(self.index)?;
+        self.index += 1;
+        Some(scored_move.mv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    #[test]
+    fn mvv_lva_tests() {
+        let board = Board::from_fen("4k3/8/2q1r3/3P4/8/8/8/2Q1K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        let pawn_takes_queen = Move::new(D5, C6, Piece::Pawn, None, true, false, false, false);
+        #[rustfmt::skip]
+        let queen_takes_queen = Move::new(C1, C6, Piece::Queen, None, true, false, false, false);
+        #[rustfmt::skip]
+        let pawn_takes_rook = Move::new(D5, E6, Piece::Pawn, None, true, false, false, false);
+
+        assert!(mvv_lva(&board, pawn_takes_queen) > mvv_lva(&board, queen_takes_queen));
+        assert!(mvv_lva(&board, queen_takes_queen) > mvv_lva(&board, pawn_takes_rook));
+    }
+
+    #[test]
+    fn move_picker_tests() {
+        let board = Board::from_fen("4k3/8/2q5/3P4/8/8/8/R3K3 w - - 0 1").unwrap();
+        #[rustfmt::skip]
+        let hash_move = Move::new(A1, A7, Piece::Rook, None, false, false, false, false);
+        #[rustfmt::skip]
+        let capture = Move::new(D5, C6, Piece::Pawn, None, true, false, false, false);
+        #[rustfmt::skip]
+        let killer = Move::new(A1, A8, Piece::Rook, None, false, false, false, false);
+        #[rustfmt::skip]
+        let good_history = Move::new(E1, D2, Piece::King, None, false, false, false, false);
+
+        let mut history = History::default();
+        history.update(Side::White, good_history, 4);
+        let killers = [None, Some(killer)];
+
+        let moves = board.generate_legal_moves();
+        let count = moves.len();
+        let picked: Vec<Move> =
+            MovePicker::new(&board, moves, Some(hash_move), killers, &history).collect();
+        assert_eq!(picked.len(), count);
+        assert_eq!(picked[..4], [hash_move, capture, killer, good_history]);
+    }
+
+    #[test]
+    fn killers_and_history_tests() {
+        #[rustfmt::skip]
+        let first = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+        #[rustfmt::skip]
+        let second = Move::new(D2, D4, Piece::Pawn, None, false, true, false, false);
+
+        let mut killers = Killers::default();
+        killers.store(3, first);
+        killers.store(3, first);
+        assert_eq!(killers.get(3), [Some(first), None]);
+        killers.store(3, second);
+        assert_eq!(killers.get(3), [Some(second), Some(first)]);
+        assert_eq!(killers.get(4), [None, None]);
+
+        let mut history = History::default();
+        history.update(Side::White, first, 3);
+        assert_eq!(history.get(Side::White, first), 9);
+        assert_eq!(history.get(Side::Black, first), 0);
+
+        // the table is scaled down once an entry grows too big
+        for _ in 0..=HISTORY_MAX / 100 {
+            history.update(Side::White, second, 10);
+        }
+        assert!(history.get(Side::White, second) <= HISTORY_MAX);
+        assert_eq!(history.get(Side::White, first), 4);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    sync::{atomic::Ordering, Arc},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::{Search, SearchLimits, SearchResult, SearchSignals};
+
+// how long a finished search sleeps between two looks at the signals
+const WAIT_INTERVAL: Duration = Duration::from_millis(1);
+
+// runs one search at a time on a worker thread the engine can stop at any point
+#[derive(Default)]
+pub struct SearchThread {
+    signals: Arc<SearchSignals>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SearchThread {
+    // any search still running is stopped first, `on_finish` is called from the worker thread
+    pub fn start<F>(&mut self, search: Search, limits: SearchLimits, on_finish: F)
+    where
+        F: FnOnce(SearchResult) + Send + 'static,
+    {
+        self.stop();
+        self.signals.stop.store(false, Ordering::Relaxed);
+        self.signals.ponder.store(limits.ponder, Ordering::Relaxed);
+
+        let signals = Arc::clone(&self.signals);
+        let mut search = search.with_signals(Arc::clone(&self.signals));
+        self.handle = Some(thread::spawn(move || {
+            let infinite = limits.infinite;
+            let result = search.run_with_limits(limits);
+
+            // the gui ends infinite and ponder searches itself, the result has to wait for it
+            while (infinite || signals.ponder.load(Ordering::Relaxed))
+                && !signals.stop.load(Ordering::Relaxed)
+            {
+                thread::sleep(WAIT_INTERVAL);
+            }
+            on_finish(result);
+        }));
+    }
+
+    // returns once the result of the search has been handed out
+    pub fn stop(&mut self) {
+        self.signals.stop.store(true, Ordering::Relaxed);
+        self.wait();
+    }
+
+    // the opponent played the expected move, the search goes on under the normal time limits
+    pub fn ponderhit(&self) {
+        self.signals.ponder.store(false, Ordering::Relaxed);
+    }
+
+    pub fn wait(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            // a panicking search has nothing left to report
+            let _ = handle.join();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+impl Drop for SearchThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use std::sync::mpsc;
+
+    #[test]
+    fn search_thread_tests() {
+        let mut thread = SearchThread::default();
+        let (sender, receiver) = mpsc::channel();
+
+        // an infinite search only reports once it is stopped
+        let limits = SearchLimits {
+            infinite: true,
+            ..SearchLimits::default()
+        };
+        let result_sender = sender.clone();
+        let search = Search::new(Board::default(), Arc::default());
+        thread.start(search, limits, move |result| {
+            result_sender.send(result).unwrap();
+        });
+        assert!(thread.is_running());
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        thread.stop();
+        assert!(!thread.is_running());
+        assert!(receiver.try_recv().unwrap().best_move.is_some());
+
+        // a ponder search turns into a normal one on ponderhit
+        let limits = SearchLimits {
+            depth: Some(2),
+            ponder: true,
+            ..SearchLimits::default()
+        };
+        let search = Search::new(Board::default(), Arc::default());
+        thread.start(search, limits, move |result| {
+            sender.send(result).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        thread.ponderhit();
+        thread.wait();
+        assert_eq!(receiver.try_recv().unwrap().depth, 2);
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+use crate::defs::Side;
+
+use super::SearchLimits;
+
+// kept aside on every move for the communication with the gui
+pub const DEFAULT_MOVE_OVERHEAD: Duration = Duration::from_millis(30);
+// moves the remaining time is spread over when the gui does not send `movestogo`
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+// how many times the soft budget a single iteration may take before being interrupted
+const HARD_LIMIT_FACTOR: u32 = 3;
+
+// the soft budget is checked between iterations, as the next one would likely not finish in
+// time, while the hard budget interrupts the iteration in progress
+pub struct TimeManager {
+    start: Instant,
+    soft_limit: Option<Duration>,
+    hard_limit: Option<Duration>,
+}
+
+impl TimeManager {
+    pub fn new(limits: &SearchLimits, side: Side, move_overhead: Duration) -> Self {
+        let (time, increment) = match side {
+            Side::White => (limits.wtime, limits.winc),
+            Side::Black => (limits.btime, limits.binc),
+        };
+
+        let (soft_limit, hard_limit) = match (limits.infinite, limits.movetime, time) {
+            (true, _, _) => (None, None),
+            (false, Some(movetime), _) => {
+                let budget = movetime.saturating_sub(move_overhead);
+                (Some(budget), Some(budget))
+            }
+            (false, None, Some(time)) => {
+                let available = time.saturating_sub(move_overhead);
+                let moves_to_go = limits.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+                let increment = increment.unwrap_or_default();
+
+                let soft = (available / moves_to_go + increment * 3 / 4).min(available);
+                let hard = (soft * HARD_LIMIT_FACTOR).min(available);
+                (Some(soft), Some(hard))
+            }
+            (false, None, None) => (None, None),
+        };
+
+        Self {
+            start: Instant::now(),
+            soft_limit,
+            hard_limit,
+        }
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn should_stop_iterating(&self) -> bool {
+        self.soft_limit
+            .is_some_and(|limit| self.get_elapsed() >= limit)
+    }
+
+    pub fn is_out_of_time(&self) -> bool {
+        self.hard_limit
+            .is_some_and(|limit| self.get_elapsed() >= limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: u64) -> Option<Duration> {
+        Some(Duration::from_millis(ms))
+    }
+
+    #[test]
+    fn time_manager_tests() {
+        let limits = SearchLimits {
+            movetime: millis(1000),
+            ..SearchLimits::default()
+        };
+        let time = TimeManager::new(&limits, Side::White, DEFAULT_MOVE_OVERHEAD);
+        assert_eq!(time.soft_limit, millis(970));
+        assert_eq!(time.hard_limit, millis(970));
+        let time = TimeManager::new(&limits, Side::White, Duration::from_millis(100));
+        assert_eq!(time.hard_limit, millis(900));
+
+        let limits = SearchLimits {
+            wtime: millis(60_030),
+            btime: millis(3_030),
+            binc: millis(1000),
+            ..SearchLimits::default()
+        };
+        let time = TimeManager::new(&limits, Side::White, DEFAULT_MOVE_OVERHEAD);
+        assert_eq!(time.soft_limit, millis(2000));
+        assert_eq!(time.hard_limit, millis(6000));
+        let time = TimeManager::new(&limits, Side::Black, DEFAULT_MOVE_OVERHEAD);
+        assert_eq!(time.soft_limit, millis(850));
+        assert_eq!(time.hard_limit, millis(2550));
+
+        // the last move before the time control may use the whole clock
+        let limits = SearchLimits {
+            wtime: millis(1030),
+            movestogo: Some(1),
+            ..SearchLimits::default()
+        };
+        let time = TimeManager::new(&limits, Side::White, DEFAULT_MOVE_OVERHEAD);
+        assert_eq!(time.soft_limit, millis(1000));
+        assert_eq!(time.hard_limit, millis(1000));
+
+        // a flagging clock still leaves the engine no time rather than underflowing
+        let limits = SearchLimits {
+            wtime: millis(10),
+            ..SearchLimits::default()
+        };
+        let time = TimeManager::new(&limits, Side::White, DEFAULT_MOVE_OVERHEAD);
+        assert_eq!(time.hard_limit, millis(0));
+        assert!(time.is_out_of_time());
+
+        let limits = SearchLimits {
+            wtime: millis(10),
+            infinite: true,
+            ..SearchLimits::default()
+        };
+        let time = TimeManager::new(&limits, Side::White, DEFAULT_MOVE_OVERHEAD);
+        assert!(!time.should_stop_iterating());
+        assert!(!time.is_out_of_time());
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    mem,
+    sync::atomic::{AtomicU64, AtomicU8, Ordering},
+};
+
+use crate::move_generator::movelist::Move;
+
+use super::MATE_SCORE;
+
+pub const DEFAULT_HASH_SIZE: usize = 16;
+pub const MIN_HASH_SIZE: usize = 1;
+pub const MAX_HASH_SIZE: usize = 65_536;
+
+const BUCKET_SIZE: usize = 4;
+
+// packed data layout : move (32 bits) | score (16 bits) | depth (8 bits) | bound (2 bits) | age (6 bits)
+const SCORE_SHIFT: u64 = 32;
+const DEPTH_SHIFT: u64 = 48;
+const BOUND_SHIFT: u64 = 56;
+const AGE_SHIFT: u64 = 58;
+const AGE_MASK: u8 = 0x3f;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bound {
+    Exact = 1,
+    Lower = 2,
+    Upper = 3,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TtEntry {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u8,
+    pub bound: Bound,
+}
+
+// the key is stored xored with the data so a torn write from another thread is detected on probe
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+// one bucket fills a 64 bytes cache line
+#[derive(Default)]
+#[repr(align(64))]
+struct Bucket([Slot; BUCKET_SIZE]);
+
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    age: AtomicU8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_SIZE)
+    }
+}
+
+impl TranspositionTable {
+    pub fn new(size_mb: usize) -> Self {
+        let mut tt = Self {
+            buckets: vec![],
+            age: AtomicU8::new(0),
+        };
+        tt.resize(size_mb);
+        tt
+    }
+
+    pub fn resize(&mut self, size_mb: usize) {
+        let size_mb = size_mb.clamp(MIN_HASH_SIZE, MAX_HASH_SIZE);
+        let bucket_count = size_mb * 1024 * 1024 / mem::size_of::<Bucket>();
+        self.buckets = (0..bucket_count).map(|_| Bucket::default()).collect();
+        self.age.store(0, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for bucket in self.buckets.iter() {
+            for slot in bucket.0.iter() {
+                slot.key.store(0, Ordering::Relaxed);
+                slot.data.store(0, Ordering::Relaxed);
+            }
+        }
+        self.age.store(0, Ordering::Relaxed);
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.buckets.len() * mem::size_of::<Bucket>() / (1024 * 1024)
+    }
+
+    // called once per search so entries left by older searches get replaced first
+    pub fn new_search(&self) {
+        let age = (self.age.load(Ordering::Relaxed) + 1) & AGE_MASK;
+        self.age.store(age, Ordering::Relaxed);
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<TtEntry> {
+        self.get_bucket(hash).0.iter().find_map(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            match data != 0 && slot.key.load(Ordering::Relaxed) ^ data == hash {
+                true => Some(Self::unpack(data)),
+                false => None,
+            }
+        })
+    }
+
+    pub fn store(&self, hash: u64, best_move: Option<Move>, score: i32, depth: u8, bound: Bound) {
+        let age = self.age.load(Ordering::Relaxed);
+        let bucket = self.get_bucket(hash);
+
+        // reuse the slot holding this position, or the least valuable one
+        let mut replace = &bucket.0[0];
+        let mut replace_value = i32::MAX;
+        for slot in bucket.0.iter() {
+            let data = slot.data.load(Ordering::Relaxed);
+            if data == 0 || slot.key.load(Ordering::Relaxed) ^ data == hash {
+                replace = slot;
+                break;
+            }
+            let slot_age = (data >> AGE_SHIFT) as u8;
+            let value = ((data >> DEPTH_SHIFT) as u8) as i32
+                - 8 * (age.wrapping_sub(slot_age) & AGE_MASK) as i32;
+            if value < replace_value {
+                replace = slot;
+                replace_value = value;
+            }
+        }
+
+        // keep the previous best move rather than forgetting it on a fail low
+        let best_move = best_move.or_else(|| {
+            let data = replace.data.load(Ordering::Relaxed);
+            match data != 0 && replace.key.load(Ordering::Relaxed) ^ data == hash {
+                true => Self::unpack(data).best_move,
+                false => None,
+            }
+        });
+
+        let data = Self::pack(best_move, score, depth, bound, age);
+        replace.key.store(hash ^ data, Ordering::Relaxed);
+        replace.data.store(data, Ordering::Relaxed);
+    }
+
+    // permill of the first thousand slots used by the current search
+    pub fn hashfull(&self) -> usize {
+        let age = self.age.load(Ordering::Relaxed);
+        let sample = self.buckets.iter().take(1000 / BUCKET_SIZE);
+        let used = sample
+            .flat_map(|bucket| bucket.0.iter())
+            .filter(|slot| {
+                let data = slot.data.load(Ordering::Relaxed);
+                data != 0 && (data >> AGE_SHIFT) as u8 == age
+            })
+            .count();
+        used * 1000 / (self.buckets.len().min(1000 / BUCKET_SIZE) * BUCKET_SIZE)
+    }
+
+    fn get_bucket(&self, hash: u64) -> &Bucket {
+        // multiply-shift maps the key onto the table without requiring a power of two size
+        let index = ((hash as u128 * self.buckets.len() as u128) >> 64) as usize;
+        &self.buckets[index]
+    }
+
+    fn pack(best_move: Option<Move>, score: i32, depth: u8, bound: Bound, age: u8) -> u64 {
+        best_move.map_or(0, |mv| mv.get_value() as u64)
+            | ((score as i16 as u16 as u64) << SCORE_SHIFT)
+            | ((depth as u64) << DEPTH_SHIFT)
+            | ((bound as u64) << BOUND_SHIFT)
+            | (((age & AGE_MASK) as u64) << AGE_SHIFT)
+    }
+
+    fn unpack(data: u64) -> TtEntry {
+        TtEntry {
+            best_move: match data as u32 {
+                0 => None,
+                value => Some(Move::from_value(value)),
+            },
+            score: (data >> SCORE_SHIFT) as u16 as i16 as i32,
+            depth: (data >> DEPTH_SHIFT) as u8,
+            bound: match (data >> BOUND_SHIFT) & 0x3 {
+                1 => Bound::Exact,
+                2 => Bound::Lower,
+                _ => Bound::Upper,
+            },
+        }
+    }
+}
+
+// mate scores are stored relative to the node so they stay valid wherever the position is reached
+pub fn score_to_tt(score: i32, ply: usize) -> i32 {
+    match score {
+        s if s > MATE_SCORE => s + ply as i32,
+        s if s < -MATE_SCORE => s - ply as i32,
+        s => s,
+    }
+}
+
+pub fn score_from_tt(score: i32, ply: usize) -> i32 {
+    match score {
+        s if s > MATE_SCORE => s - ply as i32,
+        s if s < -MATE_SCORE => s + ply as i32,
+        s => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::*, defs::Piece, search::MATE_VALUE};
+
+    #[test]
+    fn store_and_probe_tests() {
+        let tt = TranspositionTable::new(1);
+        let mv = Move::new(E2, E4, Piece::Pawn, None, false, true, false, false);
+
+        assert_eq!(tt.probe(0xdead_beef), None);
+        tt.store(0xdead_beef, Some(mv), -1234, 7, Bound::Lower);
+        assert_eq!(
+            tt.probe(0xdead_beef),
+            Some(TtEntry {
+                best_move: Some(mv),
+                score: -1234,
+                depth: 7,
+                bound: Bound::Lower,
+            })
+        );
+
+        // a store without a move keeps the one already known for the position
+        tt.store(0xdead_beef, None, 15, 8, Bound::Upper);
+        let entry = tt.probe(0xdead_beef).unwrap();
+        assert_eq!(entry.best_move, Some(mv));
+        assert_eq!(entry.bound, Bound::Upper);
+
+        tt.clear();
+        assert_eq!(tt.probe(0xdead_beef), None);
+    }
+
+    #[test]
+    fn bucket_replacement_tests() {
+        let tt = TranspositionTable::new(1);
+        // every key below maps to the first bucket
+        let hashes: Vec<u64> = (1..=BUCKET_SIZE as u64 + 1).collect();
+
+        for (depth, hash) in hashes.iter().enumerate().take(BUCKET_SIZE) {
+            tt.store(*hash, None, 0, depth as u8 + 1, Bound::Exact);
+        }
+        // the bucket is full, the shallowest entry makes room for the new one
+        tt.store(hashes[BUCKET_SIZE], None, 0, 10, Bound::Exact);
+        assert_eq!(tt.probe(hashes[0]), None);
+        for hash in hashes.iter().skip(1) {
+            assert!(tt.probe(*hash).is_some());
+        }
+
+        // entries from an older search are replaced before deeper current ones
+        tt.new_search();
+        tt.store(hashes[0], None, 0, 1, Bound::Exact);
+        assert!(tt.probe(hashes[0]).is_some());
+        assert_eq!(tt.probe(hashes[1]), None);
+    }
+
+    #[test]
+    fn resize_tests() {
+        let mut tt = TranspositionTable::new(1);
+        assert_eq!(tt.get_size(), 1);
+        assert_eq!(tt.hashfull(), 0);
+        tt.store(42, None, 0, 1, Bound::Exact);
+        assert_eq!(tt.hashfull(), 1);
+
+        tt.resize(4);
+        assert_eq!(tt.get_size(), 4);
+        assert_eq!(tt.probe(42), None);
+
+        tt.resize(0);
+        assert_eq!(tt.get_size(), MIN_HASH_SIZE);
+    }
+
+    #[test]
+    fn mate_score_tests() {
+        assert_eq!(score_to_tt(MATE_VALUE - 5, 3), MATE_VALUE - 2);
+        assert_eq!(score_from_tt(MATE_VALUE - 2, 3), MATE_VALUE - 5);
+        assert_eq!(score_to_tt(-MATE_VALUE + 5, 3), -MATE_VALUE + 2);
+        assert_eq!(score_from_tt(-MATE_VALUE + 2, 3), -MATE_VALUE + 5);
+        assert_eq!(score_to_tt(150, 3), 150);
+    }
+
+    #[test]
+    fn mate_score_store_tests() {
+        let tt = TranspositionTable::new(1);
+        tt.store(0xdead_beef, None, MATE_VALUE - 1, 5, Bound::Exact);
+        tt.store(0xbeef_dead, None, -MATE_VALUE + 1, 5, Bound::Exact);
+        assert_eq!(tt.probe(0xdead_beef).unwrap().score, MATE_VALUE - 1);
+        assert_eq!(tt.probe(0xbeef_dead).unwrap().score, -MATE_VALUE + 1);
+    }
+}